@@ -0,0 +1,35 @@
+use super::ec2::{Ec2Manager, TaggedInstance};
+use anyhow::{Error, Result};
+use tokio::task::JoinSet;
+
+/// Concurrently list every region-proxy-tagged instance across `region_codes`,
+/// one `Ec2Manager` per region, the same fan-out/gather shape `Ec2Fleet` uses
+/// for launch and teardown. A failure in one region (e.g. no credentials for
+/// that partition) doesn't block the others; it comes back paired with the
+/// region code that produced it.
+pub async fn find_tagged_instances_all_regions(
+    region_codes: &[&str],
+) -> Vec<(String, Result<Vec<TaggedInstance>>)> {
+    let mut set = JoinSet::new();
+    for region_code in region_codes {
+        let region_code = region_code.to_string();
+        set.spawn(async move {
+            let result = async {
+                let ec2 = Ec2Manager::new(&region_code).await?;
+                ec2.find_tagged_instances().await
+            }
+            .await;
+            (region_code, result)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = set.join_next().await {
+        match joined {
+            Ok(pair) => results.push(pair),
+            Err(join_err) => results.push(("unknown".to_string(), Err(Error::new(join_err)))),
+        }
+    }
+
+    results
+}
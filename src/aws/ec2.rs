@@ -1,19 +1,132 @@
 use anyhow::{bail, Context, Result};
 use aws_sdk_ec2::types::{
-    Filter, InstanceStateName, InstanceType, IpPermission, IpRange, ResourceType, Tag,
-    TagSpecification,
+    DomainType, Filter, InstanceInterruptionBehavior, InstanceMarketOptionsRequest,
+    InstanceStateName, InstanceType, IpPermission, IpRange, MarketType, ResourceType,
+    SpotInstanceType, SpotMarketOptions, Tag, TagSpecification,
 };
 use aws_sdk_ec2::Client;
+use aws_sdk_ssm::Client as SsmClient;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, info};
 
 const RESOURCE_PREFIX: &str = "region-proxy";
 
+/// Spot Instance request parameters for a cost-reduced proxy node.
+#[derive(Debug, Clone, Default)]
+pub struct SpotOptions {
+    /// Maximum hourly price to bid. `None` lets AWS charge up to the
+    /// on-demand price.
+    pub max_price: Option<String>,
+    /// `true` for a `persistent` request that relaunches after
+    /// interruption, `false` for a `one-time` request.
+    pub persistent: bool,
+}
+
+/// An additional security group ingress rule, beyond the default SSH rule
+/// `create_security_group` always opens.
+#[derive(Debug, Clone)]
+pub struct IngressRule {
+    pub protocol: String,
+    pub port: u16,
+    pub cidr: String,
+}
+
+impl IngressRule {
+    /// Parse a `--extra-ingress` CLI argument: `<protocol>:<port>:<cidr>`,
+    /// e.g. `tcp:8443:0.0.0.0/0`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut parts = spec.splitn(3, ':');
+        let protocol = parts
+            .next()
+            .filter(|s| !s.is_empty())
+            .with_context(|| format!("Ingress rule '{}' is missing a protocol", spec))?;
+        let port: u16 = parts
+            .next()
+            .with_context(|| format!("Ingress rule '{}' is missing a port", spec))?
+            .parse()
+            .with_context(|| format!("Invalid port in ingress rule '{}'", spec))?;
+        let cidr = parts
+            .next()
+            .with_context(|| format!("Ingress rule '{}' is missing a CIDR", spec))?;
+
+        Ok(Self {
+            protocol: protocol.to_string(),
+            port,
+            cidr: cidr.to_string(),
+        })
+    }
+}
+
+/// How to restrict the SSH ingress rule `create_security_group` always
+/// creates. Defaults to auto-detecting the caller's own public IP rather
+/// than leaving the rule open to the world.
+#[derive(Debug, Clone, Default)]
+pub enum SshAccess {
+    /// Auto-detect the caller's current public IPv4 and allow only
+    /// `<ip>/32`.
+    #[default]
+    AutoDetect,
+    /// Allow only the given CIDR.
+    Cidr(String),
+    /// Allow from anywhere. Must be chosen explicitly; never the default.
+    Open,
+}
+
+/// Which address `wait_for_instance` should return once the instance is
+/// running, so private-subnet and VPN-reachable deployments (with no public
+/// IP) work the same as the default public-IP flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressKind {
+    /// The instance's auto-assigned public IPv4.
+    #[default]
+    PublicIp,
+    /// The instance's private IPv4, for VPN/VPC-internal reachability.
+    PrivateIp,
+    /// The instance's public DNS name.
+    PublicDns,
+    /// Allocate and associate a new Elastic IP, and return that instead.
+    ElasticIp,
+}
+
+impl AddressKind {
+    /// Parse a `--address-kind` CLI argument: `public-ip`, `private-ip`,
+    /// `public-dns`, or `elastic-ip`.
+    pub fn parse(kind: &str) -> Result<Self> {
+        match kind {
+            "public-ip" => Ok(Self::PublicIp),
+            "private-ip" => Ok(Self::PrivateIp),
+            "public-dns" => Ok(Self::PublicDns),
+            "elastic-ip" => Ok(Self::ElasticIp),
+            other => bail!(
+                "Unknown address kind '{}': expected public-ip, private-ip, public-dns, or elastic-ip",
+                other
+            ),
+        }
+    }
+}
+
+/// An allocated Elastic IP, tracked so it can be released again.
+#[derive(Debug, Clone)]
+pub struct ElasticIp {
+    pub allocation_id: String,
+    pub public_ip: String,
+}
+
+/// The address `wait_for_instance` resolved, plus the Elastic IP allocation
+/// id when `address_kind` was `AddressKind::ElasticIp` — callers must track
+/// this and release it on teardown, since it isn't torn down with the
+/// instance itself.
+#[derive(Debug, Clone)]
+pub struct InstanceAddress {
+    pub address: String,
+    pub elastic_ip_allocation_id: Option<String>,
+}
+
 /// EC2 Manager for handling all EC2 operations
 pub struct Ec2Manager {
     client: Client,
-    #[allow(dead_code)]
+    ssm_client: SsmClient,
     region: String,
 }
 
@@ -26,18 +139,64 @@ impl Ec2Manager {
             .await;
 
         let client = Client::new(&config);
+        let ssm_client = SsmClient::new(&config);
 
         Ok(Self {
             client,
+            ssm_client,
             region: region.to_string(),
         })
     }
 
-    /// Find the latest Amazon Linux 2023 AMI for the given architecture
+    /// Find the latest Amazon Linux 2023 AMI for the given architecture.
+    ///
+    /// Resolves via the public SSM parameter that Amazon publishes for each
+    /// AL2023 release, which is a single fast call that always points at the
+    /// current non-deprecated image. Falls back to the old `describe_images`
+    /// wildcard-and-sort approach if the parameter is missing.
     pub async fn find_latest_ami(&self, arm: bool) -> Result<String> {
         let arch = if arm { "arm64" } else { "x86_64" };
         info!("Finding latest Amazon Linux 2023 AMI for {}", arch);
 
+        match self.find_latest_ami_via_ssm(arch).await {
+            Ok(ami_id) => {
+                info!("Found AMI via SSM: {}", ami_id);
+                Ok(ami_id)
+            }
+            Err(e) => {
+                debug!("SSM parameter lookup failed, falling back to describe_images: {e}");
+                self.find_latest_ami_via_describe_images(arch).await
+            }
+        }
+    }
+
+    /// Resolve the AL2023 AMI ID from the public SSM parameter Amazon
+    /// publishes alongside each release, e.g.
+    /// `/aws/service/ami-amazon-linux-latest/al2023-ami-kernel-default-x86_64`.
+    async fn find_latest_ami_via_ssm(&self, arch: &str) -> Result<String> {
+        let parameter_name = format!("/aws/service/ami-amazon-linux-latest/al2023-ami-kernel-default-{arch}");
+
+        let resp = self
+            .ssm_client
+            .get_parameter()
+            .name(&parameter_name)
+            .send()
+            .await
+            .with_context(|| format!("Failed to read SSM parameter {parameter_name}"))?;
+
+        let ami_id = resp
+            .parameter()
+            .and_then(|p| p.value())
+            .context("SSM parameter has no value")?
+            .to_string();
+
+        Ok(ami_id)
+    }
+
+    /// Find the latest Amazon Linux 2023 AMI by listing and sorting images.
+    /// Slower, and occasionally surfaces deprecated images, but doesn't
+    /// depend on the SSM parameter existing.
+    async fn find_latest_ami_via_describe_images(&self, arch: &str) -> Result<String> {
         let resp = self
             .client
             .describe_images()
@@ -76,8 +235,16 @@ impl Ec2Manager {
         Ok(ami_id)
     }
 
-    /// Create a security group for SSH access
-    pub async fn create_security_group(&self) -> Result<String> {
+    /// Create a security group for SSH access, plus an optional extra
+    /// ingress rule (e.g. the proxy's own listening port) rather than
+    /// hardcoding only TCP/22. `ssh_access` controls who the SSH rule
+    /// admits; `SshAccess::AutoDetect` (the default) restricts it to the
+    /// caller's own public IP instead of the world.
+    pub async fn create_security_group(
+        &self,
+        ssh_access: SshAccess,
+        extra_ingress: Option<&IngressRule>,
+    ) -> Result<String> {
         let group_name = format!("{}-{}", RESOURCE_PREFIX, uuid::Uuid::new_v4());
         info!("Creating security group: {}", group_name);
 
@@ -107,8 +274,13 @@ impl Ec2Manager {
             .context("Security group has no ID")?
             .to_string();
 
-        // Add SSH ingress rule (allow from anywhere for simplicity)
-        // In production, you might want to restrict to current IP
+        let ssh_cidr = match ssh_access {
+            SshAccess::AutoDetect => format!("{}/32", Self::detect_public_ip().await?),
+            SshAccess::Cidr(cidr) => cidr,
+            SshAccess::Open => "0.0.0.0/0".to_string(),
+        };
+        info!("Restricting SSH ingress to {}", ssh_cidr);
+
         self.client
             .authorize_security_group_ingress()
             .group_id(&group_id)
@@ -117,18 +289,105 @@ impl Ec2Manager {
                     .ip_protocol("tcp")
                     .from_port(22)
                     .to_port(22)
-                    .ip_ranges(IpRange::builder().cidr_ip("0.0.0.0/0").build())
+                    .ip_ranges(IpRange::builder().cidr_ip(ssh_cidr).build())
                     .build(),
             )
             .send()
             .await
             .context("Failed to add SSH ingress rule")?;
 
+        if let Some(rule) = extra_ingress {
+            info!(
+                "Adding extra ingress rule: {}/{} from {}",
+                rule.protocol, rule.port, rule.cidr
+            );
+            self.client
+                .authorize_security_group_ingress()
+                .group_id(&group_id)
+                .ip_permissions(
+                    IpPermission::builder()
+                        .ip_protocol(&rule.protocol)
+                        .from_port(rule.port as i32)
+                        .to_port(rule.port as i32)
+                        .ip_ranges(IpRange::builder().cidr_ip(&rule.cidr).build())
+                        .build(),
+                )
+                .send()
+                .await
+                .context("Failed to add extra ingress rule")?;
+        }
+
         info!("Created security group: {}", group_id);
         Ok(group_id)
     }
 
-    /// Create a key pair and return the private key
+    /// Detect the caller's current public IPv4, for `SshAccess::AutoDetect`.
+    /// Tries the EC2 instance metadata service first (so this works
+    /// unchanged when region-proxy itself is run from an EC2 instance),
+    /// falling back to a lightweight external echo endpoint otherwise.
+    async fn detect_public_ip() -> Result<String> {
+        match Self::detect_public_ip_via_imds().await {
+            Ok(ip) => Ok(ip),
+            Err(e) => {
+                debug!(
+                    "IMDS public IP lookup failed ({}), falling back to external echo endpoint",
+                    e
+                );
+                Self::detect_public_ip_via_echo().await
+            }
+        }
+    }
+
+    /// Query IMDSv2 (token-gated instance metadata) for `public-ipv4`.
+    async fn detect_public_ip_via_imds() -> Result<String> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(2))
+            .build()?;
+
+        let token = client
+            .put("http://169.254.169.254/latest/api/token")
+            .header("X-aws-ec2-metadata-token-ttl-seconds", "21600")
+            .send()
+            .await
+            .context("Failed to request IMDSv2 token")?
+            .text()
+            .await
+            .context("Failed to read IMDSv2 token")?;
+
+        let ip = client
+            .get("http://169.254.169.254/latest/meta-data/public-ipv4")
+            .header("X-aws-ec2-metadata-token", token)
+            .send()
+            .await
+            .context("Failed to query instance metadata for public IP")?
+            .text()
+            .await
+            .context("Failed to read public IP from instance metadata")?;
+
+        Ok(ip.trim().to_string())
+    }
+
+    /// Query a lightweight external echo endpoint for the caller's public
+    /// IP, for use when not running on EC2 (so IMDS isn't reachable).
+    async fn detect_public_ip_via_echo() -> Result<String> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()?;
+
+        let ip = client
+            .get("https://checkip.amazonaws.com")
+            .send()
+            .await
+            .context("Failed to query external echo endpoint for public IP")?
+            .text()
+            .await
+            .context("Failed to read public IP from echo endpoint")?;
+
+        Ok(ip.trim().to_string())
+    }
+
+    /// Create a key pair and return the private key. AWS generates and
+    /// returns the private key material directly.
     pub async fn create_key_pair(&self) -> Result<(String, String)> {
         let key_name = format!("{}-{}", RESOURCE_PREFIX, uuid::Uuid::new_v4());
         info!("Creating key pair: {}", key_name);
@@ -161,19 +420,57 @@ impl Ec2Manager {
         Ok((key_name, private_key))
     }
 
-    /// Launch an EC2 instance
+    /// Register an in-process-generated public key (see `crate::keys`) as an
+    /// EC2 key pair, so the matching private key never leaves this machine
+    /// or passes through the AWS API. Unlike `create_key_pair`, AWS never
+    /// sees the private half.
+    pub async fn import_key_pair(&self, key_name: &str, public_key_openssh: &str) -> Result<()> {
+        info!("Importing key pair: {}", key_name);
+
+        self.client
+            .import_key_pair()
+            .key_name(key_name)
+            .public_key_material(aws_sdk_ec2::primitives::Blob::new(
+                public_key_openssh.as_bytes(),
+            ))
+            .tag_specifications(
+                TagSpecification::builder()
+                    .resource_type(ResourceType::KeyPair)
+                    .tags(
+                        Tag::builder()
+                            .key("CreatedBy")
+                            .value(RESOURCE_PREFIX)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .send()
+            .await
+            .context("Failed to import key pair")?;
+
+        info!("Imported key pair: {}", key_name);
+        Ok(())
+    }
+
+    /// Launch an EC2 instance. Pass `spot` to request a Spot Instance
+    /// instead of on-demand, for cost-reduced proxy nodes. Pass
+    /// `user_data` (already base64-encoded) to have the instance run a
+    /// cloud-init script at boot, e.g. to install and start the proxy
+    /// without an SSH round-trip after launch.
     pub async fn launch_instance(
         &self,
         ami_id: &str,
         instance_type: &str,
         security_group_id: &str,
         key_name: &str,
+        spot: Option<&SpotOptions>,
+        user_data: Option<&str>,
     ) -> Result<String> {
         info!("Launching instance: type={}, ami={}", instance_type, ami_id);
 
         let instance_type = InstanceType::from(instance_type);
 
-        let resp = self
+        let mut request = self
             .client
             .run_instances()
             .image_id(ami_id)
@@ -181,7 +478,36 @@ impl Ec2Manager {
             .min_count(1)
             .max_count(1)
             .security_group_ids(security_group_id)
-            .key_name(key_name)
+            .key_name(key_name);
+
+        if let Some(user_data) = user_data {
+            request = request.user_data(user_data);
+        }
+
+        if let Some(spot) = spot {
+            info!("Requesting Spot Instance (max_price={:?})", spot.max_price);
+
+            let mut spot_options = SpotMarketOptions::builder().instance_interruption_behavior(
+                InstanceInterruptionBehavior::Terminate,
+            );
+            spot_options = spot_options.spot_instance_type(if spot.persistent {
+                SpotInstanceType::Persistent
+            } else {
+                SpotInstanceType::OneTime
+            });
+            if let Some(max_price) = &spot.max_price {
+                spot_options = spot_options.max_price(max_price);
+            }
+
+            request = request.instance_market_options(
+                InstanceMarketOptionsRequest::builder()
+                    .market_type(MarketType::Spot)
+                    .spot_options(spot_options.build())
+                    .build(),
+            );
+        }
+
+        let resp = request
             .tag_specifications(
                 TagSpecification::builder()
                     .resource_type(ResourceType::Instance)
@@ -199,6 +525,17 @@ impl Ec2Manager {
                     )
                     .build(),
             )
+            .tag_specifications(
+                TagSpecification::builder()
+                    .resource_type(ResourceType::SpotInstancesRequest)
+                    .tags(
+                        Tag::builder()
+                            .key("CreatedBy")
+                            .value(RESOURCE_PREFIX)
+                            .build(),
+                    )
+                    .build(),
+            )
             .send()
             .await
             .context("Failed to launch instance")?;
@@ -215,8 +552,15 @@ impl Ec2Manager {
         Ok(instance_id)
     }
 
-    /// Wait for instance to be running and return its public IP
-    pub async fn wait_for_instance(&self, instance_id: &str) -> Result<String> {
+    /// Wait for instance to be running and return the address requested by
+    /// `address_kind`. `AddressKind::ElasticIp` allocates and associates a
+    /// fresh Elastic IP rather than reading an existing field off the
+    /// instance, for VPCs that don't auto-assign a public address.
+    pub async fn wait_for_instance(
+        &self,
+        instance_id: &str,
+        address_kind: AddressKind,
+    ) -> Result<InstanceAddress> {
         info!("Waiting for instance {} to be running...", instance_id);
 
         let max_attempts = 60;
@@ -246,14 +590,49 @@ impl Ec2Manager {
             );
 
             if *state == InstanceStateName::Running {
-                if let Some(ip) = instance.public_ip_address() {
-                    info!("Instance is running with IP: {}", ip);
+                let (address, elastic_ip_allocation_id) = match address_kind {
+                    AddressKind::PublicIp => {
+                        (instance.public_ip_address().map(str::to_string), None)
+                    }
+                    AddressKind::PrivateIp => {
+                        (instance.private_ip_address().map(str::to_string), None)
+                    }
+                    AddressKind::PublicDns => (
+                        instance
+                            .public_dns_name()
+                            .filter(|dns| !dns.is_empty())
+                            .map(str::to_string),
+                        None,
+                    ),
+                    AddressKind::ElasticIp => {
+                        let eip = self.allocate_elastic_ip().await?;
+                        self.associate_elastic_ip(instance_id, &eip.allocation_id)
+                            .await?;
+                        (Some(eip.public_ip), Some(eip.allocation_id))
+                    }
+                };
+
+                if let Some(address) = address {
+                    info!("Instance is running, address: {}", address);
 
-                    // Wait a bit more for SSH to be ready
+                    // Wait for SSH to actually be ready rather than sleeping
+                    // a fixed guess: poll port 22, backing off from 2s up to
+                    // a 20s cap, over an overall 2 minute budget.
                     info!("Waiting for SSH to be ready...");
-                    sleep(Duration::from_secs(15)).await;
+                    crate::proxy::wait_for_tcp_port(
+                        &address,
+                        22,
+                        Duration::from_secs(2),
+                        Duration::from_secs(20),
+                        Duration::from_secs(120),
+                    )
+                    .await
+                    .context("Timed out waiting for SSH to be ready")?;
 
-                    return Ok(ip.to_string());
+                    return Ok(InstanceAddress {
+                        address,
+                        elastic_ip_allocation_id,
+                    });
                 }
             }
 
@@ -262,12 +641,57 @@ impl Ec2Manager {
                 bail!("Instance terminated unexpectedly");
             }
 
+            if let Some(spot_request_id) = instance.spot_instance_request_id() {
+                if let Some(reason) = self.spot_request_failure(spot_request_id).await {
+                    bail!(
+                        "Spot request {} failed to fulfill: {}",
+                        spot_request_id,
+                        reason
+                    );
+                }
+            }
+
             sleep(Duration::from_secs(5)).await;
         }
 
         bail!("Timeout waiting for instance to be running");
     }
 
+    /// Check a Spot Instance request's status for a terminal failure (not
+    /// simply "still pending"), returning a human-readable reason if so.
+    async fn spot_request_failure(&self, spot_request_id: &str) -> Option<String> {
+        let resp = self
+            .client
+            .describe_spot_instance_requests()
+            .spot_instance_request_ids(spot_request_id)
+            .send()
+            .await
+            .ok()?;
+
+        let status = resp
+            .spot_instance_requests()
+            .first()?
+            .status()?
+            .clone();
+
+        let code = status.code()?;
+        let is_terminal_failure = matches!(
+            code,
+            "capacity-not-available"
+                | "price-too-low"
+                | "canceled-before-fulfillment"
+                | "bad-parameters"
+                | "system-error"
+                | "schedule-expired"
+        );
+
+        if is_terminal_failure {
+            Some(status.message().unwrap_or(code).to_string())
+        } else {
+            None
+        }
+    }
+
     /// Terminate an instance
     pub async fn terminate_instance(&self, instance_id: &str) -> Result<()> {
         info!("Terminating instance: {}", instance_id);
@@ -425,8 +849,198 @@ impl Ec2Manager {
             }
         }
 
+        // Find dangling open spot requests (e.g. their instance failed to
+        // launch, or was already terminated while the request lived on)
+        let resp = self
+            .client
+            .describe_spot_instance_requests()
+            .filters(
+                Filter::builder()
+                    .name("tag:CreatedBy")
+                    .values(RESOURCE_PREFIX)
+                    .build(),
+            )
+            .filters(
+                Filter::builder()
+                    .name("state")
+                    .values("open")
+                    .values("active")
+                    .build(),
+            )
+            .send()
+            .await?;
+
+        for req in resp.spot_instance_requests() {
+            if let Some(id) = req.spot_instance_request_id() {
+                orphaned.spot_request_ids.push(id.to_string());
+            }
+        }
+
+        // Find dangling Elastic IPs allocated for a proxy node whose
+        // instance was already terminated
+        let resp = self
+            .client
+            .describe_addresses()
+            .filters(
+                Filter::builder()
+                    .name("tag:CreatedBy")
+                    .values(RESOURCE_PREFIX)
+                    .build(),
+            )
+            .send()
+            .await?;
+
+        for addr in resp.addresses() {
+            if let Some(id) = addr.allocation_id() {
+                orphaned.elastic_ip_allocation_ids.push(id.to_string());
+            }
+        }
+
         Ok(orphaned)
     }
+
+    /// List every instance region-proxy has ever tagged in this region,
+    /// regardless of whether a local session file still tracks it. Used by
+    /// `status --all-regions` and `cleanup` to surface proxies left running
+    /// on another machine, or instances whose local state was lost.
+    pub async fn find_tagged_instances(&self) -> Result<Vec<TaggedInstance>> {
+        let resp = self
+            .client
+            .describe_instances()
+            .filters(
+                Filter::builder()
+                    .name("tag:CreatedBy")
+                    .values(RESOURCE_PREFIX)
+                    .build(),
+            )
+            .send()
+            .await
+            .context("Failed to describe tagged instances")?;
+
+        let mut instances = Vec::new();
+        for reservation in resp.reservations() {
+            for instance in reservation.instances() {
+                let Some(instance_id) = instance.instance_id() else {
+                    continue;
+                };
+                let state = instance
+                    .state()
+                    .and_then(|s| s.name())
+                    .map(|n| format!("{:?}", n))
+                    .unwrap_or_else(|| "Unknown".to_string());
+
+                instances.push(TaggedInstance {
+                    instance_id: instance_id.to_string(),
+                    region: self.region.clone(),
+                    public_ip: instance.public_ip_address().map(str::to_string),
+                    state,
+                });
+            }
+        }
+
+        Ok(instances)
+    }
+
+    /// Cancel a Spot Instance request.
+    pub async fn cancel_spot_request(&self, spot_request_id: &str) -> Result<()> {
+        info!("Cancelling spot request: {}", spot_request_id);
+
+        self.client
+            .cancel_spot_instance_requests()
+            .spot_instance_request_ids(spot_request_id)
+            .send()
+            .await
+            .context("Failed to cancel spot request")?;
+
+        info!("Cancelled spot request");
+        Ok(())
+    }
+
+    /// Allocate a new Elastic IP (VPC-scoped), for `AddressKind::ElasticIp`
+    /// or for callers that want a stable address before launching.
+    pub async fn allocate_elastic_ip(&self) -> Result<ElasticIp> {
+        info!("Allocating Elastic IP");
+
+        let resp = self
+            .client
+            .allocate_address()
+            .domain(DomainType::Vpc)
+            .tag_specifications(
+                TagSpecification::builder()
+                    .resource_type(ResourceType::ElasticIp)
+                    .tags(
+                        Tag::builder()
+                            .key("CreatedBy")
+                            .value(RESOURCE_PREFIX)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .send()
+            .await
+            .context("Failed to allocate Elastic IP")?;
+
+        let allocation_id = resp
+            .allocation_id()
+            .context("Elastic IP has no allocation ID")?
+            .to_string();
+        let public_ip = resp
+            .public_ip()
+            .context("Elastic IP has no public IP")?
+            .to_string();
+
+        info!("Allocated Elastic IP: {} ({})", public_ip, allocation_id);
+        Ok(ElasticIp {
+            allocation_id,
+            public_ip,
+        })
+    }
+
+    /// Associate a previously allocated Elastic IP with an instance.
+    pub async fn associate_elastic_ip(&self, instance_id: &str, allocation_id: &str) -> Result<()> {
+        info!(
+            "Associating Elastic IP {} with instance {}",
+            allocation_id, instance_id
+        );
+
+        self.client
+            .associate_address()
+            .instance_id(instance_id)
+            .allocation_id(allocation_id)
+            .send()
+            .await
+            .context("Failed to associate Elastic IP")?;
+
+        Ok(())
+    }
+
+    /// Release a previously allocated Elastic IP.
+    pub async fn release_elastic_ip(&self, allocation_id: &str) -> Result<()> {
+        info!("Releasing Elastic IP: {}", allocation_id);
+
+        self.client
+            .release_address()
+            .allocation_id(allocation_id)
+            .send()
+            .await
+            .context("Failed to release Elastic IP")?;
+
+        info!("Released Elastic IP");
+        Ok(())
+    }
+}
+
+/// One region-proxy-tagged instance found by `find_tagged_instances`,
+/// regardless of whether a local session file still references it.
+#[derive(Debug, Clone)]
+pub struct TaggedInstance {
+    pub instance_id: String,
+    pub region: String,
+    pub public_ip: Option<String>,
+    /// Lifecycle state as reported by EC2 (e.g. `"Running"`, `"Stopped"`,
+    /// `"Terminated"`), rendered from `InstanceStateName`'s `Debug` impl
+    /// rather than re-deriving our own enum for the same information.
+    pub state: String,
 }
 
 #[derive(Debug, Default)]
@@ -434,6 +1048,8 @@ pub struct OrphanedResources {
     pub instance_ids: Vec<String>,
     pub security_group_ids: Vec<String>,
     pub key_pair_names: Vec<String>,
+    pub spot_request_ids: Vec<String>,
+    pub elastic_ip_allocation_ids: Vec<String>,
 }
 
 impl OrphanedResources {
@@ -441,5 +1057,7 @@ impl OrphanedResources {
         self.instance_ids.is_empty()
             && self.security_group_ids.is_empty()
             && self.key_pair_names.is_empty()
+            && self.spot_request_ids.is_empty()
+            && self.elastic_ip_allocation_ids.is_empty()
     }
 }
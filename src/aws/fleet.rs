@@ -0,0 +1,171 @@
+use super::ec2::{AddressKind, Ec2Manager, SpotOptions, SshAccess};
+use anyhow::{Error, Result};
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+/// Per-region request for one node in a fleet.
+#[derive(Debug, Clone)]
+pub struct FleetSpec {
+    pub region: String,
+    pub arm: bool,
+    pub instance_type: String,
+    pub spot: Option<SpotOptions>,
+}
+
+/// A successfully launched fleet node, along with everything `Ec2Fleet`
+/// needs to tear it back down.
+#[derive(Debug, Clone)]
+pub struct FleetNode {
+    pub region: String,
+    pub instance_id: String,
+    pub public_ip: String,
+    pub security_group_id: String,
+    pub key_pair_name: String,
+    pub private_key: String,
+}
+
+/// A fleet of EC2 instances spread across multiple regions, launched and
+/// torn down concurrently. Unlike `Ec2Manager`, which is bound to a single
+/// region, `Ec2Fleet` drives one `Ec2Manager` per region in parallel and
+/// tracks every node that launched successfully so `destroy()` can clean
+/// them all up even if other regions failed. A region whose launch fails
+/// partway is not tracked: any security group, key pair, or instance it had
+/// already created there is neither removed by `launch` nor reached by
+/// `destroy()`.
+pub struct Ec2Fleet {
+    nodes: Vec<FleetNode>,
+}
+
+impl Ec2Fleet {
+    /// Launch one node per spec, concurrently. Launch failures in one
+    /// region don't abort the others: every per-region error is collected
+    /// and returned alongside whatever nodes did come up.
+    pub async fn launch(specs: Vec<FleetSpec>) -> (Self, Vec<(String, Error)>) {
+        let mut set = JoinSet::new();
+        for spec in specs {
+            set.spawn(async move {
+                let region = spec.region.clone();
+                (region, Self::launch_one(spec).await)
+            });
+        }
+
+        let mut nodes = Vec::new();
+        let mut errors = Vec::new();
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok((region, Ok(node))) => {
+                    info!("Fleet node launched in {}: {}", region, node.instance_id);
+                    nodes.push(node);
+                }
+                Ok((region, Err(e))) => {
+                    warn!("Fleet launch failed in {}: {}", region, e);
+                    errors.push((region, e));
+                }
+                Err(join_err) => errors.push(("unknown".to_string(), Error::new(join_err))),
+            }
+        }
+
+        (Self { nodes }, errors)
+    }
+
+    async fn launch_one(spec: FleetSpec) -> Result<FleetNode> {
+        let ec2 = Ec2Manager::new(&spec.region).await?;
+
+        let ami_id = ec2.find_latest_ami(spec.arm).await?;
+        let security_group_id = ec2.create_security_group(SshAccess::AutoDetect, None).await?;
+        let (key_pair_name, private_key) = ec2.create_key_pair().await?;
+
+        let instance_id = ec2
+            .launch_instance(
+                &ami_id,
+                &spec.instance_type,
+                &security_group_id,
+                &key_pair_name,
+                spec.spot.as_ref(),
+                None,
+            )
+            .await?;
+
+        let address = ec2
+            .wait_for_instance(&instance_id, AddressKind::PublicIp)
+            .await?;
+
+        Ok(FleetNode {
+            region: spec.region,
+            instance_id,
+            public_ip: address.address,
+            security_group_id,
+            key_pair_name,
+            private_key,
+        })
+    }
+
+    /// Rebuild a fleet handle from nodes recovered elsewhere (e.g. a
+    /// persisted session file from a prior invocation), so `destroy()` can
+    /// tear down a fleet launched by a process that has since exited.
+    pub fn from_nodes(nodes: Vec<FleetNode>) -> Self {
+        Self { nodes }
+    }
+
+    /// The nodes currently tracked by this fleet.
+    pub fn nodes(&self) -> &[FleetNode] {
+        &self.nodes
+    }
+
+    /// `(region, instance_id, public_ip)` for every launched node.
+    pub fn instances(&self) -> Vec<(String, String, String)> {
+        self.nodes
+            .iter()
+            .map(|n| (n.region.clone(), n.instance_id.clone(), n.public_ip.clone()))
+            .collect()
+    }
+
+    /// Tear down every instance, security group, and key pair across every
+    /// region in the fleet, concurrently. Aggregates per-region errors
+    /// instead of aborting on the first failure.
+    pub async fn destroy(self) -> Vec<(String, Error)> {
+        let mut set = JoinSet::new();
+        for node in self.nodes {
+            set.spawn(async move {
+                let region = node.region.clone();
+                (region, Self::destroy_one(node).await)
+            });
+        }
+
+        let mut errors = Vec::new();
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok((region, node_errors)) => {
+                    errors.extend(node_errors.into_iter().map(|e| (region.clone(), e)));
+                }
+                Err(join_err) => errors.push(("unknown".to_string(), Error::new(join_err))),
+            }
+        }
+
+        errors
+    }
+
+    async fn destroy_one(node: FleetNode) -> Vec<Error> {
+        let mut errors = Vec::new();
+
+        let ec2 = match Ec2Manager::new(&node.region).await {
+            Ok(ec2) => ec2,
+            Err(e) => {
+                errors.push(e);
+                return errors;
+            }
+        };
+
+        if let Err(e) = ec2.terminate_instance(&node.instance_id).await {
+            errors.push(e);
+        }
+        if let Err(e) = ec2.delete_security_group(&node.security_group_id).await {
+            errors.push(e);
+        }
+        if let Err(e) = ec2.delete_key_pair(&node.key_pair_name).await {
+            errors.push(e);
+        }
+
+        errors
+    }
+}
@@ -0,0 +1,10 @@
+pub mod discovery;
+pub mod ec2;
+pub mod fleet;
+
+pub use discovery::find_tagged_instances_all_regions;
+pub use ec2::{
+    AddressKind, Ec2Manager, ElasticIp, IngressRule, InstanceAddress, OrphanedResources,
+    SpotOptions, SshAccess, TaggedInstance,
+};
+pub use fleet::{Ec2Fleet, FleetNode, FleetSpec};
@@ -33,17 +33,80 @@ pub enum Commands {
         /// Skip macOS system proxy configuration
         #[arg(long)]
         no_system_proxy: bool,
+
+        /// Run the SSH tunnel under a supervising loop that auto-reconnects
+        /// on failure instead of exiting once connected. Blocks in the
+        /// foreground until interrupted.
+        #[arg(long)]
+        supervise: bool,
+
+        /// Name for this session, so multiple proxies can run at once
+        /// (default: the --profile name if given, else derived from region
+        /// and port)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Launch profile from ~/.region-proxy/profiles.yaml, providing
+        /// region/port/instance-type defaults plus an optional post-connect
+        /// `spawn` command (e.g. a browser already pointed at the tunnel)
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// SSH forward to set up in place of the default `-D` SOCKS proxy on
+        /// `--port` (which is only added when no `--forward` is given; pass
+        /// `D:<port>` to keep it). May be repeated. Format: `D:<bind_port>` for dynamic
+        /// SOCKS, or `L:<bind_port>:<target_host>:<target_port>` /
+        /// `R:<bind_port>:<target_host>:<target_port>` for local/remote
+        /// forwards, with an optional `/udp` suffix to relay over UDP.
+        #[arg(long = "forward")]
+        forwards: Vec<String>,
+
+        /// Launch a Spot Instance instead of on-demand, for a cost-reduced
+        /// proxy node. Interruption just means `region-proxy start` again.
+        #[arg(long)]
+        spot: bool,
+
+        /// Maximum hourly price to bid for the Spot Instance (implies
+        /// --spot). Defaults to the on-demand price if not set.
+        #[arg(long)]
+        spot_max_price: Option<String>,
+
+        /// Path to a cloud-init user-data script to run on first boot (e.g.
+        /// to install and start the proxy without an SSH round-trip).
+        #[arg(long)]
+        user_data_file: Option<String>,
+
+        /// Extra security group ingress rule, beyond the default SSH rule.
+        /// Format: `<protocol>:<port>:<cidr>`, e.g. `tcp:8443:0.0.0.0/0`.
+        #[arg(long)]
+        extra_ingress: Option<String>,
+
+        /// Which address to connect the tunnel to once the instance is
+        /// running: public-ip (default), private-ip, public-dns, or
+        /// elastic-ip (allocates and associates a fresh Elastic IP).
+        #[arg(long)]
+        address_kind: Option<String>,
     },
 
-    /// Stop the running proxy and cleanup AWS resources
+    /// Stop a running proxy and cleanup its AWS resources
     Stop {
         /// Force cleanup even if some operations fail
         #[arg(short, long)]
         force: bool,
+
+        /// Which session to stop, by name (required if more than one is running)
+        #[arg(short, long)]
+        name: Option<String>,
     },
 
     /// Show the current proxy status
-    Status,
+    Status {
+        /// Also scan every region in parallel for region-proxy-tagged
+        /// instances, not just locally tracked sessions, flagging any with
+        /// no matching local session file as reclaimable
+        #[arg(long)]
+        all_regions: bool,
+    },
 
     /// List available AWS regions
     ListRegions {
@@ -64,6 +127,50 @@ pub enum Commands {
         #[command(subcommand)]
         action: ConfigAction,
     },
+
+    /// Launch and manage a multi-region fleet of instances (see `aws::Ec2Fleet`)
+    Fleet {
+        #[command(subcommand)]
+        action: FleetAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum FleetAction {
+    /// Launch one instance per region, concurrently
+    Launch {
+        /// Name for this fleet, so multiple fleets can run at once
+        #[arg(short, long)]
+        name: String,
+
+        /// Region to include in the fleet. May be repeated, e.g.
+        /// `--region us-west-2 --region ap-northeast-1`.
+        #[arg(long = "region", required = true)]
+        regions: Vec<String>,
+
+        /// EC2 instance type for every node (default: the region's own
+        /// recommended default, same as `start`)
+        #[arg(short, long)]
+        instance_type: Option<String>,
+
+        /// Launch every node as a Spot Instance instead of on-demand
+        #[arg(long)]
+        spot: bool,
+    },
+
+    /// Show every tracked fleet and its nodes
+    Status,
+
+    /// Tear down every instance, security group, and key pair in a fleet
+    Destroy {
+        /// Which fleet to destroy, by name (required if more than one exists)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Force cleanup even if some operations fail
+        #[arg(short, long)]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -119,11 +226,29 @@ mod tests {
                 port,
                 instance_type,
                 no_system_proxy,
+                supervise,
+                name,
+                profile,
+                forwards,
+                spot,
+                spot_max_price,
+                user_data_file,
+                extra_ingress,
+                address_kind,
             } => {
                 assert_eq!(region, Some("ap-northeast-1".to_string()));
                 assert!(port.is_none());
                 assert!(instance_type.is_none());
                 assert!(!no_system_proxy);
+                assert!(!supervise);
+                assert!(name.is_none());
+                assert!(profile.is_none());
+                assert!(forwards.is_empty());
+                assert!(!spot);
+                assert!(spot_max_price.is_none());
+                assert!(user_data_file.is_none());
+                assert!(extra_ingress.is_none());
+                assert!(address_kind.is_none());
             }
             _ => panic!("Expected Start command"),
         }
@@ -138,11 +263,29 @@ mod tests {
                 port,
                 instance_type,
                 no_system_proxy,
+                supervise,
+                name,
+                profile,
+                forwards,
+                spot,
+                spot_max_price,
+                user_data_file,
+                extra_ingress,
+                address_kind,
             } => {
                 assert!(region.is_none());
                 assert!(port.is_none());
                 assert!(instance_type.is_none());
                 assert!(!no_system_proxy);
+                assert!(!supervise);
+                assert!(name.is_none());
+                assert!(profile.is_none());
+                assert!(forwards.is_empty());
+                assert!(!spot);
+                assert!(spot_max_price.is_none());
+                assert!(user_data_file.is_none());
+                assert!(extra_ingress.is_none());
+                assert!(address_kind.is_none());
             }
             _ => panic!("Expected Start command"),
         }
@@ -160,6 +303,24 @@ mod tests {
             "--instance-type",
             "t3.micro",
             "--no-system-proxy",
+            "--supervise",
+            "--name",
+            "us-west",
+            "--profile",
+            "tokyo-browsing",
+            "--forward",
+            "L:8080:example.com:80",
+            "--forward",
+            "D:1080",
+            "--spot",
+            "--spot-max-price",
+            "0.01",
+            "--user-data-file",
+            "/tmp/bootstrap.sh",
+            "--extra-ingress",
+            "tcp:8443:0.0.0.0/0",
+            "--address-kind",
+            "elastic-ip",
         ]);
         match cli.command {
             Commands::Start {
@@ -167,11 +328,32 @@ mod tests {
                 port,
                 instance_type,
                 no_system_proxy,
+                supervise,
+                name,
+                profile,
+                forwards,
+                spot,
+                spot_max_price,
+                user_data_file,
+                extra_ingress,
+                address_kind,
             } => {
                 assert_eq!(region, Some("us-west-2".to_string()));
                 assert_eq!(port, Some(8080));
                 assert_eq!(instance_type, Some("t3.micro".to_string()));
                 assert!(no_system_proxy);
+                assert!(supervise);
+                assert_eq!(name, Some("us-west".to_string()));
+                assert_eq!(profile, Some("tokyo-browsing".to_string()));
+                assert_eq!(
+                    forwards,
+                    vec!["L:8080:example.com:80".to_string(), "D:1080".to_string()]
+                );
+                assert!(spot);
+                assert_eq!(spot_max_price, Some("0.01".to_string()));
+                assert_eq!(user_data_file, Some("/tmp/bootstrap.sh".to_string()));
+                assert_eq!(extra_ingress, Some("tcp:8443:0.0.0.0/0".to_string()));
+                assert_eq!(address_kind, Some("elastic-ip".to_string()));
             }
             _ => panic!("Expected Start command"),
         }
@@ -181,8 +363,9 @@ mod tests {
     fn test_cli_parse_stop() {
         let cli = Cli::parse_from(["region-proxy", "stop"]);
         match cli.command {
-            Commands::Stop { force } => {
+            Commands::Stop { force, name } => {
                 assert!(!force);
+                assert!(name.is_none());
             }
             _ => panic!("Expected Stop command"),
         }
@@ -192,8 +375,21 @@ mod tests {
     fn test_cli_parse_stop_force() {
         let cli = Cli::parse_from(["region-proxy", "stop", "--force"]);
         match cli.command {
-            Commands::Stop { force } => {
+            Commands::Stop { force, name } => {
                 assert!(force);
+                assert!(name.is_none());
+            }
+            _ => panic!("Expected Stop command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_stop_with_name() {
+        let cli = Cli::parse_from(["region-proxy", "stop", "--name", "us-west"]);
+        match cli.command {
+            Commands::Stop { force, name } => {
+                assert!(!force);
+                assert_eq!(name, Some("us-west".to_string()));
             }
             _ => panic!("Expected Stop command"),
         }
@@ -202,7 +398,19 @@ mod tests {
     #[test]
     fn test_cli_parse_status() {
         let cli = Cli::parse_from(["region-proxy", "status"]);
-        assert!(matches!(cli.command, Commands::Status));
+        match cli.command {
+            Commands::Status { all_regions } => assert!(!all_regions),
+            _ => panic!("Expected Status command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_status_all_regions() {
+        let cli = Cli::parse_from(["region-proxy", "status", "--all-regions"]);
+        match cli.command {
+            Commands::Status { all_regions } => assert!(all_regions),
+            _ => panic!("Expected Status command"),
+        }
     }
 
     #[test]
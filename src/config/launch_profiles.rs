@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// A child process to launch once the tunnel comes up, pre-wired to route
+/// through it via `HTTPS_PROXY`/`ALL_PROXY`. Torn down again in `cmd_stop`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpawnConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub envs: HashMap<String, String>,
+}
+
+/// One named "open app through region X" launch profile: region/port/
+/// instance-type defaults plus an optional post-connect `spawn` command.
+/// Distinct from [`crate::config::Profile`], which only holds flag
+/// defaults — launch profiles live in their own config file
+/// (`~/.region-proxy/profiles.yaml`) and can additionally launch an app
+/// already wired to the tunnel.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchProfile {
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub instance_type: Option<String>,
+    #[serde(default)]
+    pub spawn: Option<SpawnConfig>,
+}
+
+/// The full set of named launch profiles loaded from
+/// `~/.region-proxy/profiles.yaml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchProfiles {
+    #[serde(flatten)]
+    pub profiles: HashMap<String, LaunchProfile>,
+}
+
+impl LaunchProfiles {
+    /// Path to the YAML file users hand-edit to define launch profiles.
+    pub fn file_path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        Ok(home.join(".region-proxy").join("profiles.yaml"))
+    }
+
+    /// Load all launch profiles, or an empty set if the file doesn't exist
+    /// yet (unlike `Preferences`, there's nothing to auto-create here —
+    /// this file is meant to be hand-written).
+    pub fn load() -> Result<Self> {
+        let path = Self::file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        serde_yaml::from_str(&content).with_context(|| format!("Failed to parse {:?} as YAML", path))
+    }
+
+    /// Look up a named launch profile.
+    pub fn get(&self, name: &str) -> Option<&LaunchProfile> {
+        self.profiles.get(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profile_with_spawn() {
+        let yaml = r#"
+tokyo-browsing:
+  region: ap-northeast-1
+  port: 1080
+  instance_type: t4g.nano
+  spawn:
+    command: open
+    args: ["-a", "Google Chrome"]
+    envs:
+      FOO: bar
+"#;
+        let profiles: LaunchProfiles = serde_yaml::from_str(yaml).unwrap();
+        let profile = profiles.get("tokyo-browsing").unwrap();
+        assert_eq!(profile.region, Some("ap-northeast-1".to_string()));
+        assert_eq!(profile.port, Some(1080));
+        assert_eq!(profile.instance_type, Some("t4g.nano".to_string()));
+
+        let spawn = profile.spawn.as_ref().unwrap();
+        assert_eq!(spawn.command, "open");
+        assert_eq!(spawn.args, vec!["-a".to_string(), "Google Chrome".to_string()]);
+        assert_eq!(spawn.envs.get("FOO"), Some(&"bar".to_string()));
+    }
+
+    #[test]
+    fn test_parse_profile_without_spawn() {
+        let yaml = r#"
+minimal:
+  region: us-west-2
+"#;
+        let profiles: LaunchProfiles = serde_yaml::from_str(yaml).unwrap();
+        let profile = profiles.get("minimal").unwrap();
+        assert_eq!(profile.region, Some("us-west-2".to_string()));
+        assert!(profile.port.is_none());
+        assert!(profile.spawn.is_none());
+    }
+
+    #[test]
+    fn test_empty_profiles_document_is_empty() {
+        // Not exercising the real home directory here, just the shape: an
+        // empty document still parses to an empty profile set.
+        let profiles: LaunchProfiles = serde_yaml::from_str("{}").unwrap();
+        assert!(profiles.get("anything").is_none());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod launch_profiles;
+pub mod preferences;
+pub mod regions;
+
+pub use launch_profiles::{LaunchProfile, LaunchProfiles, SpawnConfig};
+pub use preferences::{Preferences, Profile};
+pub use regions::{find_region, is_valid_region, RegionInfo, REGIONS};
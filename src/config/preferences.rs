@@ -1,11 +1,19 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
-/// User preferences for region-proxy
+/// Env var that selects a profile for a single invocation without changing
+/// the persisted active profile.
+const PROFILE_ENV_VAR: &str = "REGION_PROXY_PROFILE";
+
+/// The default profile name used when none has been set yet.
+const DEFAULT_PROFILE: &str = "default";
+
+/// One named set of user preferences for region-proxy.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct Preferences {
+pub struct Profile {
     /// Default AWS region for proxy
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_region: Option<String>,
@@ -23,6 +31,55 @@ pub struct Preferences {
     pub no_system_proxy: Option<bool>,
 }
 
+impl Profile {
+    /// Set default region
+    pub fn set_default_region(&mut self, region: Option<String>) {
+        self.default_region = region;
+    }
+
+    /// Set default port
+    pub fn set_default_port(&mut self, port: Option<u16>) {
+        self.default_port = port;
+    }
+
+    /// Set default instance type
+    pub fn set_default_instance_type(&mut self, instance_type: Option<String>) {
+        self.default_instance_type = instance_type;
+    }
+
+    /// Set no_system_proxy preference
+    pub fn set_no_system_proxy(&mut self, no_system_proxy: Option<bool>) {
+        self.no_system_proxy = no_system_proxy;
+    }
+
+    /// Check if any preferences are set
+    pub fn is_empty(&self) -> bool {
+        self.default_region.is_none()
+            && self.default_port.is_none()
+            && self.default_instance_type.is_none()
+            && self.no_system_proxy.is_none()
+    }
+}
+
+/// User preferences for region-proxy: a set of named profiles plus which one
+/// is active. This lets users juggling multiple AWS accounts or regions
+/// keep separate sets of defaults instead of editing a single flat config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preferences {
+    pub active_profile: String,
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            active_profile: DEFAULT_PROFILE.to_string(),
+            profiles: HashMap::new(),
+        }
+    }
+}
+
 impl Preferences {
     /// Get the preferences file path
     pub fn config_file_path() -> Result<PathBuf> {
@@ -32,15 +89,32 @@ impl Preferences {
         Ok(config_dir.join("config.json"))
     }
 
-    /// Load preferences from file
+    /// Load preferences from file, auto-wrapping a legacy flat config (one
+    /// set of fields, no `profiles` key) into a `"default"` profile.
     pub fn load() -> Result<Self> {
         let path = Self::config_file_path()?;
         if !path.exists() {
             return Ok(Self::default());
         }
         let content = fs::read_to_string(&path)?;
-        let prefs: Self = serde_json::from_str(&content)?;
-        Ok(prefs)
+        let value: serde_json::Value = serde_json::from_str(&content)?;
+        Self::from_json_value(value)
+    }
+
+    /// Parse the config file's JSON, wrapping a legacy flat config into a
+    /// `"default"` profile.
+    fn from_json_value(value: serde_json::Value) -> Result<Self> {
+        if value.get("profiles").is_some() {
+            Ok(serde_json::from_value(value)?)
+        } else {
+            let legacy_profile: Profile = serde_json::from_value(value)?;
+            let mut profiles = HashMap::new();
+            profiles.insert(DEFAULT_PROFILE.to_string(), legacy_profile);
+            Ok(Self {
+                active_profile: DEFAULT_PROFILE.to_string(),
+                profiles,
+            })
+        }
     }
 
     /// Save preferences to file
@@ -58,32 +132,35 @@ impl Preferences {
         Ok(path.exists())
     }
 
-    /// Set default region
-    pub fn set_default_region(&mut self, region: Option<String>) {
-        self.default_region = region;
+    /// The profile name to use for this invocation: `REGION_PROXY_PROFILE`
+    /// overrides the persisted active profile without changing it.
+    pub fn resolve_active_profile_name(&self) -> String {
+        std::env::var(PROFILE_ENV_VAR).unwrap_or_else(|_| self.active_profile.clone())
     }
 
-    /// Set default port
-    pub fn set_default_port(&mut self, port: Option<u16>) {
-        self.default_port = port;
+    /// The resolved active profile, or an empty one if it doesn't exist yet.
+    pub fn active_profile(&self) -> Profile {
+        self.load_profile(&self.resolve_active_profile_name())
     }
 
-    /// Set default instance type
-    pub fn set_default_instance_type(&mut self, instance_type: Option<String>) {
-        self.default_instance_type = instance_type;
+    /// Load a named profile, or an empty one if it doesn't exist yet.
+    pub fn load_profile(&self, name: &str) -> Profile {
+        self.profiles.get(name).cloned().unwrap_or_default()
     }
 
-    /// Set no_system_proxy preference
-    pub fn set_no_system_proxy(&mut self, no_system_proxy: Option<bool>) {
-        self.no_system_proxy = no_system_proxy;
+    /// Get a mutable handle to a named profile, creating it if needed.
+    pub fn profile_mut(&mut self, name: &str) -> &mut Profile {
+        self.profiles.entry(name.to_string()).or_default()
     }
 
-    /// Check if any preferences are set
-    pub fn is_empty(&self) -> bool {
-        self.default_region.is_none()
-            && self.default_port.is_none()
-            && self.default_instance_type.is_none()
-            && self.no_system_proxy.is_none()
+    /// Set the persisted active profile.
+    pub fn set_active_profile(&mut self, name: impl Into<String>) {
+        self.active_profile = name.into();
+    }
+
+    /// List the names of all known profiles.
+    pub fn list_profiles(&self) -> Vec<&str> {
+        self.profiles.keys().map(String::as_str).collect()
     }
 }
 
@@ -92,92 +169,118 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_default_preferences() {
-        let prefs = Preferences::default();
-        assert!(prefs.default_region.is_none());
-        assert!(prefs.default_port.is_none());
-        assert!(prefs.default_instance_type.is_none());
-        assert!(prefs.no_system_proxy.is_none());
-        assert!(prefs.is_empty());
+    fn test_default_profile() {
+        let profile = Profile::default();
+        assert!(profile.default_region.is_none());
+        assert!(profile.default_port.is_none());
+        assert!(profile.default_instance_type.is_none());
+        assert!(profile.no_system_proxy.is_none());
+        assert!(profile.is_empty());
     }
 
     #[test]
-    fn test_serialize_deserialize() {
-        let prefs = Preferences {
+    fn test_serialize_deserialize_profile() {
+        let profile = Profile {
             default_region: Some("ap-northeast-1".to_string()),
             default_port: Some(8080),
             default_instance_type: Some("t4g.micro".to_string()),
             no_system_proxy: Some(true),
         };
 
-        let json = serde_json::to_string(&prefs).unwrap();
-        let deserialized: Preferences = serde_json::from_str(&json).unwrap();
+        let json = serde_json::to_string(&profile).unwrap();
+        let deserialized: Profile = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(prefs.default_region, deserialized.default_region);
-        assert_eq!(prefs.default_port, deserialized.default_port);
+        assert_eq!(profile.default_region, deserialized.default_region);
+        assert_eq!(profile.default_port, deserialized.default_port);
         assert_eq!(
-            prefs.default_instance_type,
+            profile.default_instance_type,
             deserialized.default_instance_type
         );
-        assert_eq!(prefs.no_system_proxy, deserialized.no_system_proxy);
+        assert_eq!(profile.no_system_proxy, deserialized.no_system_proxy);
     }
 
     #[test]
-    fn test_serialize_empty_preferences() {
-        let prefs = Preferences::default();
-        let json = serde_json::to_string(&prefs).unwrap();
-        // Empty preferences should serialize to empty object
+    fn test_serialize_empty_profile() {
+        let profile = Profile::default();
+        let json = serde_json::to_string(&profile).unwrap();
         assert_eq!(json, "{}");
     }
 
     #[test]
-    fn test_serialize_partial_preferences() {
-        let prefs = Preferences {
-            default_region: Some("us-west-2".to_string()),
-            default_port: None,
-            default_instance_type: None,
-            no_system_proxy: None,
-        };
+    fn test_profile_setters() {
+        let mut profile = Profile::default();
 
-        let json = serde_json::to_string_pretty(&prefs).unwrap();
-        assert!(json.contains("default_region"));
-        assert!(json.contains("us-west-2"));
-        assert!(!json.contains("default_port"));
-        assert!(!json.contains("default_instance_type"));
-        assert!(!json.contains("no_system_proxy"));
+        profile.set_default_region(Some("eu-west-1".to_string()));
+        assert_eq!(profile.default_region, Some("eu-west-1".to_string()));
+
+        profile.set_default_port(Some(9999));
+        assert_eq!(profile.default_port, Some(9999));
+
+        profile.set_default_instance_type(Some("t3.micro".to_string()));
+        assert_eq!(profile.default_instance_type, Some("t3.micro".to_string()));
+
+        profile.set_no_system_proxy(Some(true));
+        assert_eq!(profile.no_system_proxy, Some(true));
     }
 
     #[test]
-    fn test_is_empty() {
-        let mut prefs = Preferences::default();
-        assert!(prefs.is_empty());
+    fn test_default_preferences_has_no_profiles() {
+        let prefs = Preferences::default();
+        assert_eq!(prefs.active_profile, "default");
+        assert!(prefs.profiles.is_empty());
+        assert!(prefs.active_profile().is_empty());
+    }
+
+    #[test]
+    fn test_legacy_flat_config_migrates_to_default_profile() {
+        let flat = serde_json::json!({
+            "default_region": "ap-northeast-1",
+            "default_port": 1080,
+        });
+        let value: serde_json::Value = flat;
+        assert!(value.get("profiles").is_none());
 
-        prefs.default_region = Some("ap-northeast-1".to_string());
-        assert!(!prefs.is_empty());
+        let prefs = Preferences::from_json_value(value).unwrap();
+        assert_eq!(prefs.active_profile, "default");
 
-        prefs.default_region = None;
-        prefs.default_port = Some(1080);
-        assert!(!prefs.is_empty());
+        let active = prefs.load_profile("default");
+        assert_eq!(active.default_region, Some("ap-northeast-1".to_string()));
+        assert_eq!(active.default_port, Some(1080));
     }
 
     #[test]
-    fn test_setters() {
+    fn test_set_active_profile_and_list_profiles() {
         let mut prefs = Preferences::default();
+        prefs.profile_mut("work").set_default_region(Some("us-west-2".to_string()));
+        prefs.profile_mut("personal").set_default_region(Some("ap-northeast-1".to_string()));
+        prefs.set_active_profile("work");
 
-        prefs.set_default_region(Some("eu-west-1".to_string()));
-        assert_eq!(prefs.default_region, Some("eu-west-1".to_string()));
+        assert_eq!(prefs.active_profile, "work");
+        assert_eq!(
+            prefs.active_profile().default_region,
+            Some("us-west-2".to_string())
+        );
 
-        prefs.set_default_port(Some(9999));
-        assert_eq!(prefs.default_port, Some(9999));
+        let mut names = prefs.list_profiles();
+        names.sort();
+        assert_eq!(names, vec!["personal", "work"]);
+    }
+
+    #[test]
+    fn test_profile_env_var_overrides_active_profile_for_one_call() {
+        let mut prefs = Preferences::default();
+        prefs.profile_mut("default").set_default_region(Some("us-east-1".to_string()));
+        prefs.profile_mut("ci").set_default_region(Some("eu-central-1".to_string()));
 
-        prefs.set_default_instance_type(Some("t3.micro".to_string()));
+        std::env::set_var(PROFILE_ENV_VAR, "ci");
+        assert_eq!(prefs.resolve_active_profile_name(), "ci");
         assert_eq!(
-            prefs.default_instance_type,
-            Some("t3.micro".to_string())
+            prefs.active_profile().default_region,
+            Some("eu-central-1".to_string())
         );
+        std::env::remove_var(PROFILE_ENV_VAR);
 
-        prefs.set_no_system_proxy(Some(true));
-        assert_eq!(prefs.no_system_proxy, Some(true));
+        assert_eq!(prefs.resolve_active_profile_name(), "default");
     }
 
     #[test]
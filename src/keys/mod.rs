@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use ssh_key::rand_core::OsRng;
+use ssh_key::{Algorithm, LineEnding, PrivateKey};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An in-process generated Ed25519 keypair, written to `~/.region-proxy/keys/`.
+pub struct KeyPair {
+    pub private_key_path: PathBuf,
+    pub public_key_path: PathBuf,
+    /// OpenSSH-formatted public key, ready to register as an EC2 key pair.
+    pub public_key_openssh: String,
+}
+
+/// Directory holding generated key material.
+pub fn keys_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not find home directory")?;
+    let dir = home.join(".region-proxy").join("keys");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Generate a new Ed25519 keypair named `name`, writing the private key at
+/// mode `0600` and the public key at `0644`. Pure Rust via the `ssh-key`
+/// crate: no dependency on a system `ssh-keygen`.
+pub fn generate(name: &str) -> Result<KeyPair> {
+    let dir = keys_dir()?;
+
+    let private_key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519)
+        .context("Failed to generate Ed25519 keypair")?;
+
+    let private_key_path = dir.join(format!("{}.pem", name));
+    let public_key_path = dir.join(format!("{}.pub", name));
+
+    let private_pem = private_key
+        .to_openssh(LineEnding::LF)
+        .context("Failed to encode private key")?;
+    fs::write(&private_key_path, private_pem.as_str())?;
+    set_permissions(&private_key_path, 0o600)?;
+
+    let public_key_openssh = private_key
+        .public_key()
+        .to_openssh()
+        .context("Failed to encode public key")?;
+    fs::write(&public_key_path, format!("{}\n", public_key_openssh))?;
+    set_permissions(&public_key_path, 0o644)?;
+
+    Ok(KeyPair {
+        private_key_path,
+        public_key_path,
+        public_key_openssh,
+    })
+}
+
+fn set_permissions(path: &Path, mode: u32) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(path)?.permissions();
+        perms.set_mode(mode);
+        fs::set_permissions(path, perms)?;
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, mode);
+    }
+    Ok(())
+}
+
+/// Generate a new keypair named `new_name` to replace `old_name`, then
+/// retire (delete) the old one's local files. Does not register the new
+/// public key anywhere (e.g. as an EC2 key pair) nor deregister the old one;
+/// the caller does that with the returned `public_key_openssh`.
+pub fn rotate(old_name: &str, new_name: &str) -> Result<KeyPair> {
+    let new_key = generate(new_name)?;
+    retire(old_name)?;
+    Ok(new_key)
+}
+
+/// Delete the private and public key files for `name`.
+pub fn retire(name: &str) -> Result<()> {
+    let dir = keys_dir()?;
+    let _ = fs::remove_file(dir.join(format!("{}.pem", name)));
+    let _ = fs::remove_file(dir.join(format!("{}.pub", name)));
+    Ok(())
+}
+
+/// Delete keys for instances no longer referenced by any tracked
+/// `ProxyState` or `FleetState`, returning the number of key files removed
+/// (a keypair's `.pem` and `.pub` count separately).
+pub fn prune() -> Result<usize> {
+    let dir = keys_dir()?;
+
+    let mut referenced: HashSet<String> = crate::state::ProxyManager::list()?
+        .into_iter()
+        .map(|state| state.key_pair_name)
+        .collect();
+    referenced.extend(
+        crate::state::FleetManager::list()?
+            .into_iter()
+            .flat_map(|fleet| fleet.nodes)
+            .map(|node| node.key_pair_name),
+    );
+
+    let mut pruned = 0;
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if is_prunable(&path, &referenced) {
+            fs::remove_file(&path)?;
+            pruned += 1;
+        }
+    }
+
+    Ok(pruned)
+}
+
+/// Whether `path` is a key file (`.pem`/`.pub`) whose stem isn't in
+/// `referenced`. Split out of `prune()` so the referenced/unreferenced split
+/// is unit-testable without touching the filesystem.
+fn is_prunable(path: &Path, referenced: &HashSet<String>) -> bool {
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    if referenced.contains(stem) {
+        return false;
+    }
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("pem") | Some("pub")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn referenced() -> HashSet<String> {
+        ["in-use-key", "fleet-node-key"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_referenced_key_is_not_prunable() {
+        let referenced = referenced();
+        assert!(!is_prunable(Path::new("/keys/in-use-key.pem"), &referenced));
+        assert!(!is_prunable(Path::new("/keys/in-use-key.pub"), &referenced));
+    }
+
+    #[test]
+    fn test_referenced_fleet_key_is_not_prunable() {
+        // A key referenced only by a fleet node (not a ProxyManager session)
+        // must still be protected, or prune() would delete keys out from
+        // under a running fleet.
+        let referenced = referenced();
+        assert!(!is_prunable(
+            Path::new("/keys/fleet-node-key.pem"),
+            &referenced
+        ));
+    }
+
+    #[test]
+    fn test_unreferenced_key_is_prunable() {
+        let referenced = referenced();
+        assert!(is_prunable(Path::new("/keys/orphaned-key.pem"), &referenced));
+        assert!(is_prunable(Path::new("/keys/orphaned-key.pub"), &referenced));
+    }
+
+    #[test]
+    fn test_non_key_file_is_never_prunable() {
+        let referenced = referenced();
+        assert!(!is_prunable(
+            Path::new("/keys/orphaned-key.json"),
+            &referenced
+        ));
+    }
+}
@@ -1,14 +1,17 @@
 mod aws;
 mod cli;
 mod config;
+mod keys;
 mod proxy;
 mod state;
 
 use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use chrono::Utc;
 use clap::Parser;
-use cli::{Cli, Commands};
-use config::{find_region, REGIONS};
+use cli::{Cli, Commands, ConfigAction, FleetAction};
+use config::{find_region, is_valid_region, LaunchProfile, LaunchProfiles, Preferences, Profile, REGIONS};
 use state::ProxyState;
 use std::fs;
 use tracing::{error, info, warn, Level};
@@ -36,14 +39,57 @@ async fn main() -> Result<()> {
             port,
             instance_type,
             no_system_proxy,
+            supervise,
+            name,
+            profile,
+            forwards,
+            spot,
+            spot_max_price,
+            user_data_file,
+            extra_ingress,
+            address_kind,
         } => {
-            cmd_start(&region, port, instance_type.as_deref(), !no_system_proxy).await?;
+            let user_data = user_data_file
+                .map(|path| {
+                    fs::read(&path)
+                        .with_context(|| format!("Failed to read user-data file {:?}", path))
+                })
+                .transpose()?
+                .map(|bytes| BASE64.encode(bytes));
+            let extra_ingress = extra_ingress
+                .as_deref()
+                .map(aws::IngressRule::parse)
+                .transpose()?;
+            let address_kind = address_kind
+                .as_deref()
+                .map(aws::AddressKind::parse)
+                .transpose()?
+                .unwrap_or(aws::AddressKind::PublicIp);
+
+            cmd_start(
+                region.as_deref(),
+                port,
+                instance_type.as_deref(),
+                no_system_proxy,
+                supervise,
+                name,
+                profile,
+                forwards,
+                (spot || spot_max_price.is_some()).then(|| aws::SpotOptions {
+                    max_price: spot_max_price,
+                    persistent: false,
+                }),
+                user_data,
+                extra_ingress,
+                address_kind,
+            )
+            .await?;
         }
-        Commands::Stop { force } => {
-            cmd_stop(force).await?;
+        Commands::Stop { force, name } => {
+            cmd_stop(name, force).await?;
         }
-        Commands::Status => {
-            cmd_status().await?;
+        Commands::Status { all_regions } => {
+            cmd_status(all_regions).await?;
         }
         Commands::ListRegions { detailed } => {
             cmd_list_regions(detailed);
@@ -51,21 +97,119 @@ async fn main() -> Result<()> {
         Commands::Cleanup { region } => {
             cmd_cleanup(region.as_deref()).await?;
         }
+        Commands::Config { action } => {
+            cmd_config(action)?;
+        }
+        Commands::Fleet { action } => {
+            cmd_fleet(action).await?;
+        }
     }
 
     Ok(())
 }
 
+/// Resolve the AWS region to use, following the same precedence order the
+/// AWS CLI itself uses, with the `--profile` launch profile slotted in
+/// between an explicit flag and the saved config: an explicit `--region`
+/// flag wins, then the launch profile's `region`, then the saved config
+/// value, then `AWS_REGION`/`AWS_DEFAULT_REGION`, then the `region` property
+/// of the active profile in `~/.aws/config` (honoring `AWS_PROFILE` and
+/// `AWS_CONFIG_FILE`). This lets users who already have an AWS CLI profile
+/// set up just run `region-proxy start` with no flags.
+async fn resolve_region(
+    cli_region: Option<&str>,
+    launch_profile: Option<&LaunchProfile>,
+    profile: &Profile,
+) -> Result<String> {
+    if let Some(region) = cli_region {
+        return Ok(region.to_string());
+    }
+    if let Some(region) = launch_profile.and_then(|p| p.region.as_deref()) {
+        return Ok(region.to_string());
+    }
+    if let Some(region) = &profile.default_region {
+        return Ok(region.clone());
+    }
+
+    use aws_config::environment::EnvironmentVariableRegionProvider;
+    use aws_config::meta::region::RegionProviderChain;
+    use aws_config::profile::ProfileFileRegionProvider;
+
+    let chain = RegionProviderChain::first_try(EnvironmentVariableRegionProvider::new())
+        .or_else(ProfileFileRegionProvider::default());
+
+    chain
+        .region()
+        .await
+        .map(|region| region.to_string())
+        .context(
+            "No AWS region configured. Pass --region, run 'region-proxy config set-region \
+             <region>', set AWS_REGION/AWS_DEFAULT_REGION, or set a region in ~/.aws/config.",
+        )
+}
+
+/// `--port` wins, then the launch profile's `port`, then the saved config
+/// value, then the proxy's own default.
+fn resolve_port(cli_port: Option<u16>, launch_profile: Option<&LaunchProfile>, profile: &Profile) -> u16 {
+    cli_port
+        .or(launch_profile.and_then(|p| p.port))
+        .or(profile.default_port)
+        .unwrap_or(1080)
+}
+
+/// `--instance-type` wins, then the launch profile's `instance_type`, then
+/// the saved config value, then the region's own recommended default (ARM
+/// where available, else x86_64).
+fn resolve_instance_type<'a>(
+    cli_instance_type: Option<&'a str>,
+    launch_profile: Option<&'a LaunchProfile>,
+    profile: &'a Profile,
+    region_info: &'a config::RegionInfo,
+) -> &'a str {
+    cli_instance_type
+        .or(launch_profile.and_then(|p| p.instance_type.as_deref()))
+        .or(profile.default_instance_type.as_deref())
+        .unwrap_or_else(|| region_info.default_instance_type())
+}
+
+/// `--no-system-proxy` wins (it can only force the proxy off), then the
+/// saved config value, then the default of leaving the system proxy on.
+fn resolve_no_system_proxy(cli_no_system_proxy: bool, profile: &Profile) -> bool {
+    cli_no_system_proxy || profile.no_system_proxy.unwrap_or(false)
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn cmd_start(
-    region: &str,
-    port: u16,
+    region: Option<&str>,
+    port: Option<u16>,
     instance_type: Option<&str>,
-    enable_system_proxy: bool,
+    no_system_proxy: bool,
+    supervise: bool,
+    name: Option<String>,
+    profile_name: Option<String>,
+    forward_specs: Vec<String>,
+    spot: Option<aws::SpotOptions>,
+    user_data: Option<String>,
+    extra_ingress: Option<aws::IngressRule>,
+    address_kind: aws::AddressKind,
 ) -> Result<()> {
-    // Check if already running
-    if ProxyState::is_running()? {
-        bail!("A proxy is already running. Use 'region-proxy stop' first.");
-    }
+    let prefs = Preferences::load()?;
+    let profile = prefs.active_profile();
+
+    let launch_profiles = LaunchProfiles::load()?;
+    let launch_profile = match profile_name.as_deref() {
+        Some(n) => Some(launch_profiles.get(n).cloned().with_context(|| {
+            format!(
+                "No launch profile named '{}' in {:?}",
+                n,
+                LaunchProfiles::file_path().unwrap_or_default()
+            )
+        })?),
+        None => None,
+    };
+
+    let region = resolve_region(region, launch_profile.as_ref(), &profile).await?;
+    let region = region.as_str();
 
     // Validate region
     let region_info = find_region(region).with_context(|| {
@@ -75,11 +219,29 @@ async fn cmd_start(
         )
     })?;
 
-    let instance_type = instance_type.unwrap_or(region_info.default_instance_type());
+    let port = resolve_port(port, launch_profile.as_ref(), &profile);
+    let instance_type =
+        resolve_instance_type(instance_type, launch_profile.as_ref(), &profile, region_info);
+    let enable_system_proxy = !resolve_no_system_proxy(no_system_proxy, &profile);
     let is_arm = instance_type.starts_with("t4g")
         || instance_type.starts_with("m7g")
         || instance_type.starts_with("c7g");
 
+    // A session name lets multiple proxies run at once; default to the
+    // launch profile name if one was given (so `--profile tokyo-browsing`
+    // alone is a sensible session name), else `<region>-<port>` so a single
+    // unnamed proxy per region+port still behaves the way it always has.
+    let name = name
+        .or_else(|| profile_name.clone())
+        .unwrap_or_else(|| format!("{}-{}", region, port));
+    if state::ProxyManager::get(&name)?.is_some() {
+        bail!(
+            "A proxy session named '{}' is already running. Use 'region-proxy stop --name {}' first.",
+            name,
+            name
+        );
+    }
+
     info!("🚀 Starting proxy in {} ({})", region_info.name, region);
     info!("   Instance type: {}", instance_type);
     info!("   Local port: {}", port);
@@ -93,27 +255,40 @@ async fn cmd_start(
 
     // Create security group
     info!("🔒 Creating security group...");
-    let sg_id = ec2.create_security_group().await?;
-
-    // Create key pair
-    info!("🔑 Creating key pair...");
-    let (key_name, private_key) = ec2.create_key_pair().await?;
+    let sg_id = ec2
+        .create_security_group(aws::SshAccess::AutoDetect, extra_ingress.as_ref())
+        .await?;
 
-    // Save key to file
-    let keys_dir = ProxyState::keys_dir()?;
-    let key_path = keys_dir.join(format!("{}.pem", key_name));
-    fs::write(&key_path, &private_key)?;
+    // Generate a keypair in-process (see `crate::keys`) and register its
+    // public half as an EC2 key pair, rather than asking AWS to generate the
+    // private key and ship it back to us.
+    info!("🔑 Generating key pair...");
+    let key_name = format!("region-proxy-{}", uuid::Uuid::new_v4());
+    let keypair = keys::generate(&key_name)?;
+    ec2.import_key_pair(&key_name, &keypair.public_key_openssh)
+        .await?;
+    let key_path = keypair.private_key_path;
 
     // Launch instance
     info!("🖥️  Launching EC2 instance...");
     let instance_id = ec2
-        .launch_instance(&ami_id, instance_type, &sg_id, &key_name)
+        .launch_instance(
+            &ami_id,
+            instance_type,
+            &sg_id,
+            &key_name,
+            spot.as_ref(),
+            user_data.as_deref(),
+        )
         .await?;
 
     // Wait for instance
     info!("⏳ Waiting for instance to be ready...");
-    let public_ip = match ec2.wait_for_instance(&instance_id).await {
-        Ok(ip) => ip,
+    let address = match ec2
+        .wait_for_instance(&instance_id, address_kind)
+        .await
+    {
+        Ok(address) => address,
         Err(e) => {
             // Cleanup on failure
             error!("Failed to wait for instance: {}", e);
@@ -121,17 +296,74 @@ async fn cmd_start(
             let _ = ec2.terminate_instance(&instance_id).await;
             let _ = ec2.delete_security_group(&sg_id).await;
             let _ = ec2.delete_key_pair(&key_name).await;
-            let _ = fs::remove_file(&key_path);
+            let _ = keys::retire(&key_name);
             return Err(e);
         }
     };
+    let public_ip = address.address;
+    let elastic_ip_allocation_id = address.elastic_ip_allocation_id;
+
+    // `--forward` lets callers request arbitrary `-L`/`-R`/UDP forwards
+    // alongside (or instead of) the default `-D` SOCKS proxy on `--port`.
+    let mut forwards = forward_specs
+        .iter()
+        .map(|spec| proxy::ForwardSpec::parse(spec))
+        .collect::<Result<Vec<_>>>()?;
+    if forwards.is_empty() {
+        forwards.push(proxy::ForwardSpec::dynamic(port));
+    }
+    let has_system_proxy_forward = forwards
+        .iter()
+        .any(|f| f.direction == proxy::ForwardDirection::Dynamic && f.bind_port == port);
+    if enable_system_proxy && !has_system_proxy_forward {
+        warn!(
+            "System proxy was not disabled with --no-system-proxy, but --forward didn't \
+             include a dynamic SOCKS forward on port {}, so there's nothing to point it at. \
+             Skipping system proxy configuration.",
+            port
+        );
+    }
+    let enable_system_proxy = enable_system_proxy && has_system_proxy_forward;
 
-    // Start SSH tunnel
     info!("🔗 Starting SSH tunnel...");
-    let ssh_pid = proxy::start_ssh_tunnel(&public_ip, &key_path, port, "ec2-user")?;
+    if supervise {
+        return cmd_start_supervised(
+            name,
+            region,
+            region_info.name,
+            port,
+            instance_id,
+            public_ip,
+            sg_id,
+            key_name,
+            key_path,
+            forwards,
+            enable_system_proxy,
+            launch_profile.and_then(|p| p.spawn),
+            elastic_ip_allocation_id,
+        )
+        .await;
+    }
 
-    // Wait for tunnel
-    proxy::wait_for_tunnel(port).await?;
+    let tunnel = proxy::start_ssh_tunnel(&public_ip, &key_path, &forwards, "ec2-user").await?;
+    let ssh_pid = tunnel.pid();
+    let control_socket = tunnel.control_socket().map(|p| p.to_path_buf());
+    // The tunnel handle itself isn't persisted across process restarts; we
+    // keep just enough (PID or control socket path) in `ProxyState` to find
+    // and tear it down again from a future invocation.
+    std::mem::forget(tunnel);
+
+    // Wait for every local TCP forward to come up (UDP forwards are relayed
+    // through a socat process, not a directly-listening TCP port, so they
+    // aren't polled here; `request_forward` already confirmed them
+    // synchronously when the tunnel was set up). `-R` forwards listen on the
+    // remote host, so there is no local port to poll for them either.
+    for spec in forwards
+        .iter()
+        .filter(|f| !f.is_udp() && f.direction != proxy::ForwardDirection::RemoteToLocal)
+    {
+        proxy::wait_for_tunnel(spec.bind_port).await?;
+    }
 
     // Enable system proxy
     if enable_system_proxy {
@@ -139,8 +371,19 @@ async fn cmd_start(
         proxy::enable_socks_proxy(port)?;
     }
 
+    // Launch the profile's post-connect command, if any
+    let spawn_pid = match launch_profile.and_then(|p| p.spawn) {
+        Some(spawn_cfg) => {
+            info!("🚀 Launching '{}' through the tunnel...", spawn_cfg.command);
+            let child = proxy::spawn_after_connect(&spawn_cfg, port)?;
+            Some(child.id())
+        }
+        None => None,
+    };
+
     // Save state
     let state = ProxyState {
+        name: name.clone(),
         instance_id: instance_id.clone(),
         region: region.to_string(),
         public_ip: public_ip.clone(),
@@ -148,37 +391,243 @@ async fn cmd_start(
         key_pair_name: key_name,
         key_path,
         local_port: port,
-        ssh_pid: Some(ssh_pid),
+        ssh_pid,
+        control_socket,
+        forwards,
+        reconnect_count: 0,
+        recent_log_lines: std::collections::VecDeque::new(),
+        spawn_pid,
+        elastic_ip_allocation_id,
+        supervisor_pid: None,
         started_at: Utc::now(),
     };
-    state.save()?;
+    state::ProxyManager::add(&state)?;
 
     println!();
     println!("✅ Proxy is ready!");
     println!();
+    println!("   Name:      {}", name);
     println!("   Region:    {} ({})", region_info.name, region);
     println!("   Public IP: {}", public_ip);
     println!("   SOCKS:     localhost:{}", port);
     println!();
-    println!("   To stop:   region-proxy stop");
+    println!("   To stop:   region-proxy stop --name {}", name);
     println!();
 
     Ok(())
 }
 
-async fn cmd_stop(force: bool) -> Result<()> {
-    let state = match ProxyState::load()? {
-        Some(s) => s,
-        None => {
-            if force {
-                warn!("No active proxy found, but --force was specified. Skipping.");
-                return Ok(());
+/// Run the SSH tunnel under `proxy::run_supervised` instead of the normal
+/// fire-and-forget flow: blocks in the foreground, persisting the pid,
+/// reconnect count, and buffered log lines into `ProxyState` on every
+/// respawn, until interrupted with Ctrl-C.
+#[allow(clippy::too_many_arguments)]
+async fn cmd_start_supervised(
+    name: String,
+    region: &str,
+    region_name: &str,
+    port: u16,
+    instance_id: String,
+    public_ip: String,
+    security_group_id: String,
+    key_pair_name: String,
+    key_path: std::path::PathBuf,
+    forwards: Vec<proxy::ForwardSpec>,
+    enable_system_proxy: bool,
+    spawn: Option<config::SpawnConfig>,
+    elastic_ip_allocation_id: Option<String>,
+) -> Result<()> {
+    use tokio::sync::{mpsc, oneshot};
+
+    let (update_tx, mut update_rx) = mpsc::unbounded_channel::<proxy::SupervisorUpdate>();
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+
+    let sup_host = public_ip.clone();
+    let sup_key_path = key_path.clone();
+    let sup_forwards = forwards.clone();
+    let supervisor_task = tokio::spawn(async move {
+        proxy::run_supervised(
+            &sup_host,
+            &sup_key_path,
+            &sup_forwards,
+            "ec2-user",
+            move |update| {
+                let _ = update_tx.send(update);
+                Ok(())
+            },
+            cancel_rx,
+        )
+        .await
+    });
+
+    let first = update_rx
+        .recv()
+        .await
+        .context("Supervisor exited before the tunnel ever came up")?;
+
+    for spec in forwards
+        .iter()
+        .filter(|f| !f.is_udp() && f.direction != proxy::ForwardDirection::RemoteToLocal)
+    {
+        proxy::wait_for_tunnel(spec.bind_port).await?;
+    }
+
+    if enable_system_proxy {
+        info!("🌐 Configuring system proxy...");
+        proxy::enable_socks_proxy(port)?;
+    }
+
+    let spawn_pid = match spawn {
+        Some(spawn_cfg) => {
+            info!("🚀 Launching '{}' through the tunnel...", spawn_cfg.command);
+            let child = proxy::spawn_after_connect(&spawn_cfg, port)?;
+            Some(child.id())
+        }
+        None => None,
+    };
+
+    let mut state = ProxyState {
+        name: name.clone(),
+        instance_id,
+        region: region.to_string(),
+        public_ip: public_ip.clone(),
+        security_group_id,
+        key_pair_name,
+        key_path,
+        local_port: port,
+        ssh_pid: Some(first.pid),
+        control_socket: None,
+        forwards,
+        reconnect_count: first.reconnect_count,
+        recent_log_lines: first.recent_log_lines,
+        spawn_pid,
+        elastic_ip_allocation_id,
+        supervisor_pid: Some(std::process::id()),
+        started_at: Utc::now(),
+    };
+    state::ProxyManager::add(&state)?;
+
+    println!();
+    println!("✅ Proxy is ready (supervised)!");
+    println!();
+    println!("   Name:      {}", name);
+    println!("   Region:    {} ({})", region_name, region);
+    println!("   Public IP: {}", public_ip);
+    println!("   SOCKS:     localhost:{}", port);
+    println!();
+    println!("   Watching the tunnel. Press Ctrl-C to stop supervising");
+    println!("   (the proxy keeps running; use 'region-proxy stop' to tear it down).");
+    println!();
+
+    // `cmd_stop` (running as a separate process) delivers SIGTERM here via
+    // `supervisor_pid` to tell us to actually stop, as opposed to Ctrl-C
+    // below which only detaches: it's about to terminate the EC2 instance,
+    // so the live `ssh` child must die with us instead of being respawned
+    // by a reconnect loop that's about to lose its target.
+    #[cfg(unix)]
+    let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .context("Failed to install SIGTERM handler")?;
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                let _ = cancel_tx.send(());
+                break;
+            }
+            #[cfg(unix)]
+            _ = sigterm.recv() => {
+                let _ = cancel_tx.send(());
+                if let Some(pid) = state.ssh_pid {
+                    let _ = proxy::stop_ssh_tunnel(pid);
+                }
+                break;
+            }
+            update = update_rx.recv() => {
+                match update {
+                    Some(update) => {
+                        state.ssh_pid = Some(update.pid);
+                        state.reconnect_count = update.reconnect_count;
+                        state.recent_log_lines = update.recent_log_lines;
+                        state::ProxyManager::add(&state)?;
+                    }
+                    None => break,
+                }
             }
-            bail!("No active proxy found. Nothing to stop.");
         }
+    }
+
+    let _ = supervisor_task.await;
+    Ok(())
+}
+
+/// Tear down a ControlMaster connection by its control socket path.
+fn stop_control_master(control_socket: &std::path::Path) -> Result<()> {
+    let status = std::process::Command::new("ssh")
+        .arg("-S")
+        .arg(control_socket)
+        .arg("-O")
+        .arg("exit")
+        .arg("-") // Host is ignored by `-O exit`, but ssh still requires one.
+        .status()
+        .context("Failed to send exit control command")?;
+
+    if !status.success() {
+        bail!("ssh -O exit failed for control socket {:?}", control_socket);
+    }
+    Ok(())
+}
+
+async fn cmd_stop(name: Option<String>, force: bool) -> Result<()> {
+    let sessions = state::ProxyManager::list()?;
+    let state = match name {
+        Some(name) => match sessions.into_iter().find(|s| s.name == name) {
+            Some(s) => s,
+            None => {
+                if force {
+                    warn!(
+                        "No active proxy session named '{}', but --force was specified. Skipping.",
+                        name
+                    );
+                    return Ok(());
+                }
+                bail!(
+                    "No active proxy session named '{}'. Use 'region-proxy status' to see active sessions.",
+                    name
+                );
+            }
+        },
+        None => match sessions.len() {
+            0 => {
+                if force {
+                    warn!("No active proxy found, but --force was specified. Skipping.");
+                    return Ok(());
+                }
+                bail!("No active proxy found. Nothing to stop.");
+            }
+            1 => sessions.into_iter().next().unwrap(),
+            _ => {
+                let names: Vec<&str> = sessions.iter().map(|s| s.name.as_str()).collect();
+                bail!(
+                    "Multiple proxy sessions are running ({}). Specify which to stop with --name.",
+                    names.join(", ")
+                );
+            }
+        },
     };
 
-    info!("🛑 Stopping proxy...");
+    info!("🛑 Stopping proxy '{}'...", state.name);
+
+    // Stop the launch profile's spawned process, if any
+    if let Some(pid) = state.spawn_pid {
+        info!("🧹 Stopping spawned process...");
+        if let Err(e) = proxy::stop_spawned_process(pid) {
+            if force {
+                warn!("Failed to stop spawned process: {}", e);
+            } else {
+                return Err(e);
+            }
+        }
+    }
 
     // Disable system proxy
     info!("🌐 Disabling system proxy...");
@@ -190,9 +639,37 @@ async fn cmd_stop(force: bool) -> Result<()> {
         }
     }
 
+    // If this tunnel is running under `--supervise` in another process,
+    // signal that process first so it stops reconnecting before we tear
+    // anything down below — otherwise it just respawns `ssh` the moment we
+    // kill it and keeps retrying against an instance we're about to
+    // terminate, then re-writes the session file we're about to remove.
+    if let Some(supervisor_pid) = state.supervisor_pid {
+        info!("🛑 Signalling supervisor process (PID: {})...", supervisor_pid);
+        if let Err(e) = proxy::stop_supervisor(supervisor_pid) {
+            if force {
+                warn!("Failed to signal supervisor process: {}", e);
+            } else {
+                return Err(e);
+            }
+        } else {
+            // Give the supervisor a moment to cancel its reconnect loop and
+            // kill its ssh child before we fall through to the checks below.
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+    }
+
     // Stop SSH tunnel
     info!("🔗 Stopping SSH tunnel...");
-    if let Some(pid) = state.ssh_pid {
+    if let Some(control_socket) = &state.control_socket {
+        if let Err(e) = stop_control_master(control_socket) {
+            if force {
+                warn!("Failed to close ControlMaster socket: {}", e);
+            } else {
+                return Err(e);
+            }
+        }
+    } else if let Some(pid) = state.ssh_pid {
         if let Err(e) = proxy::stop_ssh_tunnel(pid) {
             if force {
                 warn!("Failed to stop SSH tunnel: {}", e);
@@ -216,6 +693,18 @@ async fn cmd_stop(force: bool) -> Result<()> {
         }
     }
 
+    // Release Elastic IP, if this session allocated one
+    if let Some(allocation_id) = &state.elastic_ip_allocation_id {
+        info!("🌐 Releasing Elastic IP...");
+        if let Err(e) = ec2.release_elastic_ip(allocation_id).await {
+            if force {
+                warn!("Failed to release Elastic IP: {}", e);
+            } else {
+                return Err(e);
+            }
+        }
+    }
+
     // Delete security group
     info!("🔒 Deleting security group...");
     if let Err(e) = ec2.delete_security_group(&state.security_group_id).await {
@@ -236,13 +725,11 @@ async fn cmd_stop(force: bool) -> Result<()> {
         }
     }
 
-    // Delete local key file
-    if state.key_path.exists() {
-        let _ = fs::remove_file(&state.key_path);
-    }
+    // Delete local key file (both the private and public halves)
+    let _ = keys::retire(&state.key_pair_name);
 
-    // Delete state file
-    ProxyState::delete()?;
+    // Delete session file
+    state::ProxyManager::remove(&state.name)?;
 
     println!();
     println!("✅ Proxy stopped and cleaned up!");
@@ -251,40 +738,61 @@ async fn cmd_stop(force: bool) -> Result<()> {
     Ok(())
 }
 
-async fn cmd_status() -> Result<()> {
-    let state = match ProxyState::load()? {
-        Some(s) => s,
-        None => {
-            println!("No active proxy.");
-            return Ok(());
-        }
-    };
+async fn cmd_status(all_regions: bool) -> Result<()> {
+    let sessions = state::ProxyManager::list()?;
 
-    let region_info = find_region(&state.region);
-    let region_name = region_info.map(|r| r.name).unwrap_or("Unknown");
+    if all_regions {
+        return cmd_status_all_regions(&sessions).await;
+    }
 
-    let duration = Utc::now().signed_duration_since(state.started_at);
-    let hours = duration.num_hours();
-    let minutes = duration.num_minutes() % 60;
+    if sessions.is_empty() {
+        println!("No active proxy.");
+        return Ok(());
+    }
 
-    let ssh_running = proxy::find_ssh_pid(state.local_port)?.is_some();
     let proxy_enabled = proxy::is_socks_proxy_enabled().unwrap_or(false);
 
     println!();
     println!("📊 Proxy Status");
-    println!();
-    println!("   Region:      {} ({})", region_name, state.region);
-    println!("   Instance:    {}", state.instance_id);
-    println!("   Public IP:   {}", state.public_ip);
-    println!("   SOCKS:       localhost:{}", state.local_port);
-    println!(
-        "   SSH tunnel:  {}",
-        if ssh_running {
-            "✅ Running"
-        } else {
-            "❌ Not running"
+
+    for state in &sessions {
+        let region_info = find_region(&state.region);
+        let region_name = region_info.map(|r| r.name).unwrap_or("Unknown");
+
+        let duration = Utc::now().signed_duration_since(state.started_at);
+        let hours = duration.num_hours();
+        let minutes = duration.num_minutes() % 60;
+
+        let health = state.health().await;
+
+        println!();
+        println!("   Name:        {}", state.name);
+        println!("   Region:      {} ({})", region_name, state.region);
+        println!("   Instance:    {}", state.instance_id);
+        println!("   Public IP:   {}", state.public_ip);
+        println!("   SOCKS:       localhost:{}", state.local_port);
+        println!(
+            "   SSH tunnel:  {}",
+            match health {
+                state::TunnelHealth::Running => "✅ Running",
+                state::TunnelHealth::PortClosed => "⚠️  Port closed",
+                state::TunnelHealth::ProcessDead => "❌ Process dead",
+            }
+        );
+        println!("   Running for: {}h {}m", hours, minutes);
+
+        if state.reconnect_count > 0 || !state.recent_log_lines.is_empty() {
+            println!("   Reconnects:  {}", state.reconnect_count);
         }
-    );
+        if !state.recent_log_lines.is_empty() {
+            println!("   Recent SSH log lines:");
+            for line in &state.recent_log_lines {
+                println!("     {}", line);
+            }
+        }
+    }
+
+    println!();
     println!(
         "   System proxy: {}",
         if proxy_enabled {
@@ -293,12 +801,221 @@ async fn cmd_status() -> Result<()> {
             "❌ Disabled"
         }
     );
-    println!("   Running for: {}h {}m", hours, minutes);
     println!();
 
     Ok(())
 }
 
+/// Fan out a `DescribeInstances` call (filtered by region-proxy's resource
+/// tag) across every known region concurrently, reporting every tagged
+/// instance's id, region, public IP, and lifecycle state. Instances with no
+/// matching entry in `sessions` are flagged reclaimable: region-proxy
+/// created them, but no local session file is tracking them anymore (e.g.
+/// the session was started from a different machine, or its state file was
+/// lost).
+async fn cmd_status_all_regions(sessions: &[ProxyState]) -> Result<()> {
+    let region_codes: Vec<&str> = REGIONS.iter().map(|r| r.code).collect();
+    let results = aws::find_tagged_instances_all_regions(&region_codes).await;
+
+    let known_instance_ids: std::collections::HashSet<&str> =
+        sessions.iter().map(|s| s.instance_id.as_str()).collect();
+
+    println!();
+    println!("📊 Proxy Status (all regions)");
+
+    let mut found = 0;
+    for (region_code, result) in &results {
+        let instances = match result {
+            Ok(instances) => instances,
+            Err(e) => {
+                warn!("Failed to check {}: {}", region_code, e);
+                continue;
+            }
+        };
+
+        for instance in instances {
+            found += 1;
+            let region_name = find_region(region_code).map(|r| r.name).unwrap_or("Unknown");
+            let reclaimable = !known_instance_ids.contains(instance.instance_id.as_str());
+
+            println!();
+            println!("   Instance:    {}", instance.instance_id);
+            println!("   Region:      {} ({})", region_name, region_code);
+            println!(
+                "   Public IP:   {}",
+                instance.public_ip.as_deref().unwrap_or("-")
+            );
+            println!("   State:       {}", instance.state);
+            if reclaimable {
+                println!("   ⚠️  Reclaimable: no local session is tracking this instance");
+            }
+        }
+    }
+
+    if found == 0 {
+        println!();
+        println!("No region-proxy instances found in any region.");
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Dispatch a `region-proxy config <action>` subcommand.
+fn cmd_config(action: ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Show => cmd_config_show(),
+        ConfigAction::SetRegion { region } => cmd_config_set_region(region),
+        ConfigAction::SetPort { port } => cmd_config_set_port(port),
+        ConfigAction::SetInstanceType { instance_type } => {
+            cmd_config_set_instance_type(instance_type)
+        }
+        ConfigAction::SetNoSystemProxy { value } => cmd_config_set_no_system_proxy(&value),
+        ConfigAction::Unset { option } => cmd_config_unset(&option),
+        ConfigAction::Reset => cmd_config_reset(),
+    }
+}
+
+fn cmd_config_show() -> Result<()> {
+    let prefs = Preferences::load()?;
+    let profile_name = prefs.resolve_active_profile_name();
+    let profile = prefs.load_profile(&profile_name);
+
+    println!();
+    println!("⚙️  Configuration (profile: {})", profile_name);
+    println!();
+    println!(
+        "   Region:           {}",
+        profile.default_region.as_deref().unwrap_or("(not set)")
+    );
+    println!(
+        "   Port:             {}",
+        profile
+            .default_port
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "(not set)".to_string())
+    );
+    println!(
+        "   Instance type:    {}",
+        profile
+            .default_instance_type
+            .as_deref()
+            .unwrap_or("(not set)")
+    );
+    println!(
+        "   No system proxy:  {}",
+        profile
+            .no_system_proxy
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "(not set)".to_string())
+    );
+
+    let mut other_profiles: Vec<&str> = prefs
+        .list_profiles()
+        .into_iter()
+        .filter(|name| *name != profile_name)
+        .collect();
+    if !other_profiles.is_empty() {
+        other_profiles.sort();
+        println!();
+        println!("   Other profiles:   {}", other_profiles.join(", "));
+    }
+    println!();
+
+    Ok(())
+}
+
+fn cmd_config_set_region(region: String) -> Result<()> {
+    if !is_valid_region(&region) {
+        bail!(
+            "Unknown region: {}. Use 'region-proxy list-regions' to see available regions.",
+            region
+        );
+    }
+
+    let mut prefs = Preferences::load()?;
+    let profile_name = prefs.resolve_active_profile_name();
+    prefs
+        .profile_mut(&profile_name)
+        .set_default_region(Some(region.clone()));
+    prefs.save()?;
+
+    println!("✅ Default region set to {}", region);
+    Ok(())
+}
+
+fn cmd_config_set_port(port: u16) -> Result<()> {
+    let mut prefs = Preferences::load()?;
+    let profile_name = prefs.resolve_active_profile_name();
+    prefs
+        .profile_mut(&profile_name)
+        .set_default_port(Some(port));
+    prefs.save()?;
+
+    println!("✅ Default port set to {}", port);
+    Ok(())
+}
+
+fn cmd_config_set_instance_type(instance_type: String) -> Result<()> {
+    let mut prefs = Preferences::load()?;
+    let profile_name = prefs.resolve_active_profile_name();
+    prefs
+        .profile_mut(&profile_name)
+        .set_default_instance_type(Some(instance_type.clone()));
+    prefs.save()?;
+
+    println!("✅ Default instance type set to {}", instance_type);
+    Ok(())
+}
+
+fn cmd_config_set_no_system_proxy(value: &str) -> Result<()> {
+    let parsed: bool = value
+        .parse()
+        .with_context(|| format!("Invalid value '{}': expected 'true' or 'false'", value))?;
+
+    let mut prefs = Preferences::load()?;
+    let profile_name = prefs.resolve_active_profile_name();
+    prefs
+        .profile_mut(&profile_name)
+        .set_no_system_proxy(Some(parsed));
+    prefs.save()?;
+
+    println!("✅ no-system-proxy set to {}", parsed);
+    Ok(())
+}
+
+fn cmd_config_unset(option: &str) -> Result<()> {
+    let mut prefs = Preferences::load()?;
+    let profile_name = prefs.resolve_active_profile_name();
+    let profile = prefs.profile_mut(&profile_name);
+
+    match option {
+        "region" => profile.set_default_region(None),
+        "port" => profile.set_default_port(None),
+        "instance-type" => profile.set_default_instance_type(None),
+        "no-system-proxy" => profile.set_no_system_proxy(None),
+        other => bail!(
+            "Unknown config option: {}. Expected one of: region, port, instance-type, \
+             no-system-proxy",
+            other
+        ),
+    }
+    prefs.save()?;
+
+    println!("✅ Cleared {}", option);
+    Ok(())
+}
+
+fn cmd_config_reset() -> Result<()> {
+    let mut prefs = Preferences::load()?;
+    let profile_name = prefs.resolve_active_profile_name();
+    *prefs.profile_mut(&profile_name) = Profile::default();
+    prefs.save()?;
+
+    println!("✅ Configuration reset for profile '{}'", profile_name);
+    Ok(())
+}
+
 fn cmd_list_regions(detailed: bool) {
     println!();
     println!("Available AWS Regions:");
@@ -329,11 +1046,35 @@ async fn cmd_cleanup(region: Option<&str>) -> Result<()> {
         None => REGIONS.iter().map(|r| r.code).collect(),
     };
 
+    let sessions = state::ProxyManager::list()?;
+    let known_instance_ids: std::collections::HashSet<&str> =
+        sessions.iter().map(|s| s.instance_id.as_str()).collect();
+
     let mut total_cleaned = 0;
 
     for region_code in regions {
         info!("Checking region: {}", region_code);
         let ec2 = aws::Ec2Manager::new(region_code).await?;
+
+        let tagged = ec2.find_tagged_instances().await?;
+        if !tagged.is_empty() {
+            println!("region-proxy instances live in {}:", region_code);
+            for instance in &tagged {
+                let reclaimable = if known_instance_ids.contains(instance.instance_id.as_str()) {
+                    ""
+                } else {
+                    " (reclaimable: no local session)"
+                };
+                println!(
+                    "  {} [{}] {}{}",
+                    instance.instance_id,
+                    instance.state,
+                    instance.public_ip.as_deref().unwrap_or("-"),
+                    reclaimable
+                );
+            }
+        }
+
         let orphaned = ec2.find_orphaned_resources().await?;
 
         if orphaned.is_empty() {
@@ -368,6 +1109,30 @@ async fn cmd_cleanup(region: Option<&str>) -> Result<()> {
                 total_cleaned += 1;
             }
         }
+
+        for id in &orphaned.spot_request_ids {
+            println!("  Cancelling spot request: {}", id);
+            if let Err(e) = ec2.cancel_spot_request(id).await {
+                warn!("Failed to cancel spot request {}: {}", id, e);
+            } else {
+                total_cleaned += 1;
+            }
+        }
+
+        for id in &orphaned.elastic_ip_allocation_ids {
+            println!("  Releasing Elastic IP: {}", id);
+            if let Err(e) = ec2.release_elastic_ip(id).await {
+                warn!("Failed to release Elastic IP {}: {}", id, e);
+            } else {
+                total_cleaned += 1;
+            }
+        }
+    }
+
+    let pruned_keys = keys::prune()?;
+    if pruned_keys > 0 {
+        println!("Pruned {} unreferenced local key(s).", pruned_keys);
+        total_cleaned += pruned_keys;
     }
 
     if total_cleaned == 0 {
@@ -379,3 +1144,215 @@ async fn cmd_cleanup(region: Option<&str>) -> Result<()> {
 
     Ok(())
 }
+
+async fn cmd_fleet(action: FleetAction) -> Result<()> {
+    match action {
+        FleetAction::Launch {
+            name,
+            regions,
+            instance_type,
+            spot,
+        } => cmd_fleet_launch(name, regions, instance_type, spot).await,
+        FleetAction::Status => cmd_fleet_status(),
+        FleetAction::Destroy { name, force } => cmd_fleet_destroy(name, force).await,
+    }
+}
+
+/// Launch one instance per region via `aws::Ec2Fleet`, then persist the
+/// resulting nodes as a `FleetState` so `fleet status`/`fleet destroy` can
+/// find them again from a later invocation.
+async fn cmd_fleet_launch(
+    name: String,
+    regions: Vec<String>,
+    instance_type: Option<String>,
+    spot: bool,
+) -> Result<()> {
+    if state::FleetManager::get(&name)?.is_some() {
+        bail!(
+            "A fleet named '{}' already exists. Use 'region-proxy fleet destroy --name {}' first.",
+            name,
+            name
+        );
+    }
+
+    let mut specs = Vec::with_capacity(regions.len());
+    for region in &regions {
+        let region_info = find_region(region).with_context(|| {
+            format!(
+                "Unknown region: {}. Use 'region-proxy list-regions' to see available regions.",
+                region
+            )
+        })?;
+        specs.push(aws::FleetSpec {
+            region: region.clone(),
+            arm: region_info.supports_arm,
+            instance_type: instance_type
+                .clone()
+                .unwrap_or_else(|| region_info.default_instance_type().to_string()),
+            spot: spot.then(|| aws::SpotOptions {
+                max_price: None,
+                persistent: false,
+            }),
+        });
+    }
+
+    info!("🚀 Launching fleet '{}' across {} region(s)...", name, specs.len());
+    let (fleet, errors) = aws::Ec2Fleet::launch(specs).await;
+
+    for (region, error) in &errors {
+        error!("Fleet launch failed in {}: {}", region, error);
+    }
+
+    let mut nodes = Vec::with_capacity(fleet.nodes().len());
+    for node in fleet.nodes() {
+        let key_path = keys::keys_dir()?.join(format!("{}.pem", node.key_pair_name));
+        fs::write(&key_path, &node.private_key)
+            .with_context(|| format!("Failed to write key material to {:?}", key_path))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+
+        nodes.push(state::FleetNodeState {
+            region: node.region.clone(),
+            instance_id: node.instance_id.clone(),
+            public_ip: node.public_ip.clone(),
+            security_group_id: node.security_group_id.clone(),
+            key_pair_name: node.key_pair_name.clone(),
+            key_path,
+        });
+    }
+
+    if nodes.is_empty() {
+        bail!("Fleet launch failed in every region; nothing to track.");
+    }
+
+    let state = state::FleetState {
+        name: name.clone(),
+        nodes,
+        started_at: Utc::now(),
+    };
+    state::FleetManager::add(&state)?;
+
+    println!();
+    println!("✅ Fleet '{}' launched ({} node(s))!", name, state.nodes.len());
+    println!();
+    for node in &state.nodes {
+        println!(
+            "   {} [{}] {} (ssh -i {:?} ec2-user@{})",
+            node.region, node.instance_id, node.public_ip, node.key_path, node.public_ip
+        );
+    }
+    if !errors.is_empty() {
+        println!();
+        println!("⚠️  {} region(s) failed to launch; see the warnings above.", errors.len());
+    }
+    println!();
+    println!("   To stop:   region-proxy fleet destroy --name {}", name);
+    println!();
+
+    Ok(())
+}
+
+fn cmd_fleet_status() -> Result<()> {
+    let fleets = state::FleetManager::list()?;
+
+    if fleets.is_empty() {
+        println!("No active fleets.");
+        return Ok(());
+    }
+
+    println!();
+    println!("📊 Fleet Status");
+
+    for fleet in &fleets {
+        println!();
+        println!("   Name:    {}", fleet.name);
+        println!("   Started: {}", fleet.started_at.format("%Y-%m-%d %H:%M:%S UTC"));
+        println!("   Nodes:   {}", fleet.nodes.len());
+        for node in &fleet.nodes {
+            println!("     - {} [{}] {}", node.region, node.instance_id, node.public_ip);
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Tear down every instance, security group, and key pair in a fleet via
+/// `aws::Ec2Fleet::destroy`, then forget the fleet and its key material.
+async fn cmd_fleet_destroy(name: Option<String>, force: bool) -> Result<()> {
+    let fleets = state::FleetManager::list()?;
+    let state = match name {
+        Some(name) => match fleets.into_iter().find(|f| f.name == name) {
+            Some(f) => f,
+            None => {
+                if force {
+                    warn!("No active fleet named '{}', but --force was specified. Skipping.", name);
+                    return Ok(());
+                }
+                bail!(
+                    "No active fleet named '{}'. Use 'region-proxy fleet status' to see active fleets.",
+                    name
+                );
+            }
+        },
+        None => match fleets.len() {
+            0 => {
+                if force {
+                    warn!("No active fleet found, but --force was specified. Skipping.");
+                    return Ok(());
+                }
+                bail!("No active fleet found. Nothing to destroy.");
+            }
+            1 => fleets.into_iter().next().unwrap(),
+            _ => {
+                let names: Vec<&str> = fleets.iter().map(|f| f.name.as_str()).collect();
+                bail!(
+                    "Multiple fleets exist ({}). Specify which to destroy with --name.",
+                    names.join(", ")
+                );
+            }
+        },
+    };
+
+    info!("🛑 Destroying fleet '{}'...", state.name);
+
+    let fleet = aws::Ec2Fleet::from_nodes(
+        state
+            .nodes
+            .iter()
+            .map(|n| aws::FleetNode {
+                region: n.region.clone(),
+                instance_id: n.instance_id.clone(),
+                public_ip: n.public_ip.clone(),
+                security_group_id: n.security_group_id.clone(),
+                key_pair_name: n.key_pair_name.clone(),
+                private_key: String::new(),
+            })
+            .collect(),
+    );
+
+    let errors = fleet.destroy().await;
+    for (region, error) in &errors {
+        warn!("Failed to tear down resources in {}: {}", region, error);
+    }
+    if !errors.is_empty() && !force {
+        bail!(
+            "{} region(s) failed to tear down cleanly; re-run with --force to proceed anyway.",
+            errors.len()
+        );
+    }
+
+    for node in &state.nodes {
+        let _ = fs::remove_file(&node.key_path);
+    }
+    state::FleetManager::remove(&state.name)?;
+
+    println!();
+    println!("✅ Fleet destroyed and cleaned up!");
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,264 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Direction of an SSH port forward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ForwardDirection {
+    /// `-D`: dynamic SOCKS proxy, no fixed remote target.
+    Dynamic,
+    /// `-L`: bind locally, connect out through the remote host.
+    LocalToRemote,
+    /// `-R`: bind remotely, connect out through the local host.
+    RemoteToLocal,
+}
+
+/// Transport carried by a forward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ForwardProtocol {
+    Tcp,
+    Udp,
+}
+
+/// A single SSH forwarding rule, covering the `-D`/`-L`/`-R` modes `ssh`
+/// itself supports plus a UDP variant tunneled over a TCP forward.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForwardSpec {
+    pub direction: ForwardDirection,
+    pub protocol: ForwardProtocol,
+    pub bind_address: String,
+    pub bind_port: u16,
+    /// `(host, port)` on the other side of the tunnel. `None` for `Dynamic`
+    /// forwards, which have no fixed target.
+    pub target: Option<(String, u16)>,
+}
+
+impl ForwardSpec {
+    /// A `-D` dynamic SOCKS forward on `localhost:<bind_port>`.
+    pub fn dynamic(bind_port: u16) -> Self {
+        Self {
+            direction: ForwardDirection::Dynamic,
+            protocol: ForwardProtocol::Tcp,
+            bind_address: "localhost".to_string(),
+            bind_port,
+            target: None,
+        }
+    }
+
+    /// A `-L` forward: `localhost:<bind_port>` -> `target` via the remote host.
+    pub fn local_to_remote(bind_port: u16, target_host: impl Into<String>, target_port: u16) -> Self {
+        Self {
+            direction: ForwardDirection::LocalToRemote,
+            protocol: ForwardProtocol::Tcp,
+            bind_address: "localhost".to_string(),
+            bind_port,
+            target: Some((target_host.into(), target_port)),
+        }
+    }
+
+    /// A `-R` forward: remote `<bind_port>` -> `target` via the local host.
+    pub fn remote_to_local(bind_port: u16, target_host: impl Into<String>, target_port: u16) -> Self {
+        Self {
+            direction: ForwardDirection::RemoteToLocal,
+            protocol: ForwardProtocol::Tcp,
+            bind_address: "localhost".to_string(),
+            bind_port,
+            target: Some((target_host.into(), target_port)),
+        }
+    }
+
+    pub fn is_udp(&self) -> bool {
+        self.protocol == ForwardProtocol::Udp
+    }
+
+    /// The `ssh` flag (`-D`, `-L`, or `-R`) and its argument for this forward.
+    /// UDP specs must be converted to an equivalent TCP spec first (see
+    /// `tunnel::setup_udp_relay`) since `ssh` itself only carries TCP.
+    pub fn ssh_flag(&self) -> Result<(&'static str, String)> {
+        if self.protocol == ForwardProtocol::Udp {
+            bail!("UDP forward must be relayed over TCP before building an ssh flag");
+        }
+
+        match self.direction {
+            ForwardDirection::Dynamic => {
+                Ok(("-D", format!("{}:{}", self.bind_address, self.bind_port)))
+            }
+            ForwardDirection::LocalToRemote => {
+                let (host, port) = self
+                    .target
+                    .as_ref()
+                    .context("LocalToRemote forward needs a target host:port")?;
+                Ok((
+                    "-L",
+                    format!("{}:{}:{}:{}", self.bind_address, self.bind_port, host, port),
+                ))
+            }
+            ForwardDirection::RemoteToLocal => {
+                let (host, port) = self
+                    .target
+                    .as_ref()
+                    .context("RemoteToLocal forward needs a target host:port")?;
+                Ok((
+                    "-R",
+                    format!("{}:{}:{}:{}", self.bind_address, self.bind_port, host, port),
+                ))
+            }
+        }
+    }
+
+    /// Parse a `--forward` CLI argument: `D:<bind_port>` for a dynamic SOCKS
+    /// forward, or `L:<bind_port>:<target_host>:<target_port>` /
+    /// `R:<bind_port>:<target_host>:<target_port>` for local/remote forwards,
+    /// with an optional `/udp` suffix to relay the forward over UDP instead
+    /// of TCP.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (body, protocol) = match spec.strip_suffix("/udp") {
+            Some(rest) => (rest, ForwardProtocol::Udp),
+            None => (spec, ForwardProtocol::Tcp),
+        };
+
+        let mut parts = body.splitn(2, ':');
+        let mode = parts
+            .next()
+            .filter(|s| !s.is_empty())
+            .context("Forward spec needs a mode (D, L, or R), e.g. 'L:8080:example.com:80'")?;
+        let rest = parts
+            .next()
+            .context("Forward spec needs arguments after the mode, e.g. 'D:1080'")?;
+
+        match mode.to_ascii_uppercase().as_str() {
+            "D" => {
+                if protocol == ForwardProtocol::Udp {
+                    bail!("Dynamic (SOCKS) forwarding doesn't support UDP");
+                }
+                let bind_port: u16 = rest
+                    .parse()
+                    .with_context(|| format!("Invalid bind port in forward spec '{}'", spec))?;
+                Ok(Self::dynamic(bind_port))
+            }
+            mode @ ("L" | "R") => {
+                let mut fields = rest.splitn(3, ':');
+                let bind_port: u16 = fields
+                    .next()
+                    .with_context(|| format!("Forward spec '{}' is missing a bind port", spec))?
+                    .parse()
+                    .with_context(|| format!("Invalid bind port in forward spec '{}'", spec))?;
+                let target_host = fields
+                    .next()
+                    .with_context(|| format!("Forward spec '{}' is missing a target host", spec))?;
+                let target_port: u16 = fields
+                    .next()
+                    .with_context(|| format!("Forward spec '{}' is missing a target port", spec))?
+                    .parse()
+                    .with_context(|| format!("Invalid target port in forward spec '{}'", spec))?;
+
+                let mut forward = if mode == "L" {
+                    Self::local_to_remote(bind_port, target_host, target_port)
+                } else {
+                    Self::remote_to_local(bind_port, target_host, target_port)
+                };
+                forward.protocol = protocol;
+                Ok(forward)
+            }
+            other => bail!(
+                "Unknown forward mode '{}' in '{}': expected D, L, or R",
+                other,
+                spec
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_ssh_flag() {
+        let spec = ForwardSpec::dynamic(1080);
+        let (flag, arg) = spec.ssh_flag().unwrap();
+        assert_eq!(flag, "-D");
+        assert_eq!(arg, "localhost:1080");
+    }
+
+    #[test]
+    fn test_local_to_remote_ssh_flag() {
+        let spec = ForwardSpec::local_to_remote(8080, "example.com", 80);
+        let (flag, arg) = spec.ssh_flag().unwrap();
+        assert_eq!(flag, "-L");
+        assert_eq!(arg, "localhost:8080:example.com:80");
+    }
+
+    #[test]
+    fn test_remote_to_local_ssh_flag() {
+        let spec = ForwardSpec::remote_to_local(9090, "localhost", 3000);
+        let (flag, arg) = spec.ssh_flag().unwrap();
+        assert_eq!(flag, "-R");
+        assert_eq!(arg, "localhost:9090:localhost:3000");
+    }
+
+    #[test]
+    fn test_udp_spec_rejects_ssh_flag() {
+        let spec = ForwardSpec {
+            protocol: ForwardProtocol::Udp,
+            ..ForwardSpec::local_to_remote(5353, "8.8.8.8", 53)
+        };
+        assert!(spec.ssh_flag().is_err());
+    }
+
+    #[test]
+    fn test_local_to_remote_missing_target_is_error() {
+        let spec = ForwardSpec {
+            target: None,
+            ..ForwardSpec::local_to_remote(8080, "example.com", 80)
+        };
+        assert!(spec.ssh_flag().is_err());
+    }
+
+    #[test]
+    fn test_parse_dynamic() {
+        let spec = ForwardSpec::parse("D:1080").unwrap();
+        assert_eq!(spec.direction, ForwardDirection::Dynamic);
+        assert_eq!(spec.bind_port, 1080);
+        assert_eq!(spec.protocol, ForwardProtocol::Tcp);
+    }
+
+    #[test]
+    fn test_parse_local_to_remote() {
+        let spec = ForwardSpec::parse("L:8080:example.com:80").unwrap();
+        assert_eq!(spec.direction, ForwardDirection::LocalToRemote);
+        assert_eq!(spec.bind_port, 8080);
+        assert_eq!(spec.target, Some(("example.com".to_string(), 80)));
+        assert_eq!(spec.protocol, ForwardProtocol::Tcp);
+    }
+
+    #[test]
+    fn test_parse_remote_to_local_lowercase() {
+        let spec = ForwardSpec::parse("r:9090:localhost:3000").unwrap();
+        assert_eq!(spec.direction, ForwardDirection::RemoteToLocal);
+        assert_eq!(spec.bind_port, 9090);
+        assert_eq!(spec.target, Some(("localhost".to_string(), 3000)));
+    }
+
+    #[test]
+    fn test_parse_udp_suffix() {
+        let spec = ForwardSpec::parse("L:5353:8.8.8.8:53/udp").unwrap();
+        assert_eq!(spec.protocol, ForwardProtocol::Udp);
+        assert!(spec.is_udp());
+    }
+
+    #[test]
+    fn test_parse_dynamic_udp_is_error() {
+        assert!(ForwardSpec::parse("D:1080/udp").is_err());
+    }
+
+    #[test]
+    fn test_parse_unknown_mode_is_error() {
+        assert!(ForwardSpec::parse("X:1080").is_err());
+    }
+
+    #[test]
+    fn test_parse_missing_fields_is_error() {
+        assert!(ForwardSpec::parse("L:8080:example.com").is_err());
+        assert!(ForwardSpec::parse("D").is_err());
+    }
+}
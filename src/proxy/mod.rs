@@ -1,5 +1,14 @@
+pub mod forward;
 pub mod macos;
-pub mod ssh;
+pub mod spawn;
+pub mod supervisor;
+pub mod tunnel;
 
+pub use forward::{ForwardDirection, ForwardProtocol, ForwardSpec};
 pub use macos::{disable_socks_proxy, enable_socks_proxy, is_socks_proxy_enabled};
-pub use ssh::{find_ssh_pid, start_ssh_tunnel, stop_ssh_tunnel, stop_ssh_tunnel_by_port, wait_for_tunnel};
+pub use spawn::{spawn_after_connect, stop_spawned_process};
+pub use supervisor::{run_supervised, stop_supervisor, SupervisorUpdate, LOG_BUFFER_CAPACITY};
+pub use tunnel::{
+    find_ssh_pid, start_ssh_tunnel, stop_ssh_tunnel, stop_ssh_tunnel_by_port, wait_for_tcp_port,
+    wait_for_tunnel, Tunnel,
+};
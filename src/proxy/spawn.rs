@@ -0,0 +1,46 @@
+use crate::config::SpawnConfig;
+use anyhow::{Context, Result};
+use std::process::{Child, Command, Stdio};
+
+/// Launch a launch profile's post-connect command, pointed at the tunnel via
+/// `HTTPS_PROXY`/`ALL_PROXY` (and their lowercase aliases, since not every
+/// tool respects the capitalized form), with the profile's own `envs`
+/// layered on top. The child is left running independently; its pid is
+/// persisted in `ProxyState` so `cmd_stop` can tear it down later.
+pub fn spawn_after_connect(spawn: &SpawnConfig, local_port: u16) -> Result<Child> {
+    let proxy_url = format!("socks5://127.0.0.1:{}", local_port);
+
+    Command::new(&spawn.command)
+        .args(&spawn.args)
+        .env("HTTPS_PROXY", &proxy_url)
+        .env("https_proxy", &proxy_url)
+        .env("ALL_PROXY", &proxy_url)
+        .env("all_proxy", &proxy_url)
+        .envs(&spawn.envs)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to spawn '{}' after connecting", spawn.command))
+}
+
+/// Stop a process started via `spawn_after_connect`, by PID.
+pub fn stop_spawned_process(pid: u32) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        kill(Pid::from_raw(pid as i32), Signal::SIGTERM)
+            .context("Failed to send SIGTERM to spawned process")?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        Command::new("kill")
+            .arg(pid.to_string())
+            .status()
+            .context("Failed to kill spawned process")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use super::forward::ForwardSpec;
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::time::{sleep, Instant};
+use tracing::{debug, info, warn};
+
+/// Maximum number of recent SSH stderr lines retained for `cmd_status`.
+pub const LOG_BUFFER_CAPACITY: usize = 50;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// A respawned tunnel that survives this long resets backoff back to
+/// `INITIAL_BACKOFF`, so a single bad network blip doesn't leave later,
+/// unrelated drops waiting 30s to reconnect.
+const STABLE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Snapshot handed to the caller's `on_update` after every (re)spawn, so it
+/// can persist the new pid and log snapshot into `ProxyState`.
+pub struct SupervisorUpdate {
+    pub pid: u32,
+    pub reconnect_count: u32,
+    pub recent_log_lines: VecDeque<String>,
+}
+
+/// Run the SSH tunnel under a monitoring loop a la Fuchsia's host-pipe:
+/// spawn `ssh` with piped stderr, continuously classify lines to detect
+/// tunnel death (broken pipe, "Connection closed", EOF, or the child
+/// exiting), and respawn with exponential backoff. Calls `on_update` after
+/// every (re)spawn and runs until `cancel` resolves.
+///
+/// Unlike `start_ssh_tunnel`, this only supports the bare-subprocess model:
+/// the supervisor needs to own the child's stderr pipe directly, which
+/// rules out handing the connection off to a detached ControlMaster.
+pub async fn run_supervised(
+    host: &str,
+    key_path: &Path,
+    forwards: &[ForwardSpec],
+    user: &str,
+    mut on_update: impl FnMut(SupervisorUpdate) -> Result<()>,
+    mut cancel: tokio::sync::oneshot::Receiver<()>,
+) -> Result<()> {
+    let mut reconnect_count = 0u32;
+    let mut backoff = INITIAL_BACKOFF;
+    let mut log_buffer: VecDeque<String> = VecDeque::with_capacity(LOG_BUFFER_CAPACITY);
+
+    loop {
+        let mut child = spawn_ssh(host, key_path, forwards, user)?;
+        let pid = child.id().context("SSH child exited before reporting a PID")?;
+        info!("Supervised SSH tunnel spawned (PID: {})", pid);
+
+        on_update(SupervisorUpdate {
+            pid,
+            reconnect_count,
+            recent_log_lines: log_buffer.clone(),
+        })?;
+
+        let stderr = child
+            .stderr
+            .take()
+            .context("SSH child has no stderr pipe")?;
+        let mut lines = BufReader::new(stderr).lines();
+
+        let spawned_at = Instant::now();
+        let death_reason = tokio::select! {
+            _ = &mut cancel => {
+                // Cancelling the supervisor only stops the reconnect loop
+                // from watching the tunnel; it must leave the live `ssh`
+                // process running, since the proxy is meant to keep working
+                // after the user stops supervising it (torn down later via
+                // `region-proxy stop`). Dropping `child` here doesn't kill
+                // it: tokio only kills children on drop when
+                // `kill_on_drop(true)` was set, which `spawn_ssh` doesn't do.
+                info!("Supervisor cancelled, leaving tunnel running (use `region-proxy stop` to tear it down)");
+                return Ok(());
+            }
+            reason = monitor(&mut lines, &mut log_buffer) => reason,
+            status = child.wait() => format!("ssh process exited: {:?}", status),
+        };
+
+        warn!("Supervised SSH tunnel died ({}), reconnecting...", death_reason);
+        let _ = kill_child(&mut child).await;
+        reconnect_count += 1;
+
+        if spawned_at.elapsed() >= STABLE_PERIOD {
+            backoff = INITIAL_BACKOFF;
+        }
+
+        debug!("Reconnecting in {:?}", backoff);
+        sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Read stderr lines into the ring buffer (evicting the oldest once full)
+/// until a known death marker is seen or the stream ends.
+async fn monitor(
+    lines: &mut tokio::io::Lines<BufReader<tokio::process::ChildStderr>>,
+    log_buffer: &mut VecDeque<String>,
+) -> String {
+    loop {
+        match lines.next_line().await {
+            Ok(Some(line)) => {
+                debug!("ssh: {}", line);
+
+                if log_buffer.len() == LOG_BUFFER_CAPACITY {
+                    log_buffer.pop_front();
+                }
+                log_buffer.push_back(line.clone());
+
+                if is_death_line(&line) {
+                    return line;
+                }
+            }
+            Ok(None) => return "EOF on ssh stderr".to_string(),
+            Err(e) => return format!("error reading ssh stderr: {}", e),
+        }
+    }
+}
+
+/// Whether an SSH stderr line indicates the tunnel has gone down.
+fn is_death_line(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    lower.contains("broken pipe")
+        || lower.contains("connection closed")
+        || lower.contains("connection reset")
+        || lower.contains("connection refused")
+        || lower.contains("connection timed out")
+}
+
+async fn kill_child(child: &mut Child) -> Result<()> {
+    child.kill().await.context("Failed to kill ssh child")
+}
+
+/// Signal a running `run_supervised` process (by the PID it reported via
+/// `ProxyState::supervisor_pid`, not the `ssh` child's PID) to stop
+/// reconnecting and exit. Unlike cancelling in-process via the `cancel`
+/// oneshot, which leaves the tunnel running for an interactive Ctrl-C, this
+/// is for `cmd_stop`: the caller is about to terminate the EC2 instance out
+/// from under the tunnel, so the supervisor must stop respawning `ssh` and
+/// rewriting the session file first.
+#[cfg(unix)]
+pub fn stop_supervisor(pid: u32) -> Result<()> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    kill(Pid::from_raw(pid as i32), Signal::SIGTERM).context("Failed to send SIGTERM to supervisor process")
+}
+
+#[cfg(not(unix))]
+pub fn stop_supervisor(pid: u32) -> Result<()> {
+    std::process::Command::new("kill")
+        .arg(pid.to_string())
+        .status()
+        .context("Failed to stop supervisor process")?;
+    Ok(())
+}
+
+/// Spawn the foreground `ssh -N` process the supervisor owns directly,
+/// piping stderr so the monitor loop can read it line-by-line.
+fn spawn_ssh(host: &str, key_path: &Path, forwards: &[ForwardSpec], user: &str) -> Result<Child> {
+    let mut cmd = Command::new("ssh");
+    cmd.arg("-N") // No remote command
+        .arg("-o")
+        .arg("StrictHostKeyChecking=no")
+        .arg("-o")
+        .arg("UserKnownHostsFile=/dev/null")
+        .arg("-o")
+        .arg("ServerAliveInterval=15")
+        .arg("-o")
+        .arg("ServerAliveCountMax=3")
+        .arg("-o")
+        .arg("ExitOnForwardFailure=yes")
+        .arg("-i")
+        .arg(key_path);
+
+    for spec in forwards {
+        let (flag, arg) = spec.ssh_flag()?;
+        cmd.arg(flag).arg(arg);
+    }
+
+    cmd.arg(format!("{}@{}", user, host))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+
+    cmd.spawn().context("Failed to spawn supervised SSH process")
+}
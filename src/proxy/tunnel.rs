@@ -0,0 +1,509 @@
+use super::forward::{ForwardDirection, ForwardProtocol, ForwardSpec};
+use anyhow::{bail, Context, Result};
+use openssh::{KnownHosts, SessionBuilder};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use sysinfo::{Pid, System};
+use tokio::net::TcpStream;
+use tokio::time::{sleep, Instant};
+use tracing::{debug, info, warn};
+
+/// A live SSH tunnel, either a multiplexed `openssh` session holding a
+/// ControlMaster connection open, or a bare `ssh -f -N` subprocess carrying
+/// the requested `-D`/`-L`/`-R` forwards when multiplexing couldn't be set up.
+pub enum Tunnel {
+    /// A live `openssh` session. Its ControlMaster socket lets additional
+    /// forwards be layered onto the same TCP connection without a new
+    /// handshake.
+    Multiplexed {
+        session: openssh::Session,
+        control_socket: PathBuf,
+    },
+    Subprocess {
+        pid: u32,
+    },
+}
+
+impl Tunnel {
+    /// Path to the ControlMaster socket, if this tunnel is multiplexed.
+    pub fn control_socket(&self) -> Option<&Path> {
+        match self {
+            Tunnel::Multiplexed { control_socket, .. } => Some(control_socket),
+            Tunnel::Subprocess { .. } => None,
+        }
+    }
+
+    /// The subprocess PID, if this tunnel is a bare `ssh` process.
+    pub fn pid(&self) -> Option<u32> {
+        match self {
+            Tunnel::Subprocess { pid } => Some(*pid),
+            Tunnel::Multiplexed { .. } => None,
+        }
+    }
+
+    /// Check whether the underlying session or process is still alive.
+    pub async fn is_alive(&self) -> bool {
+        match self {
+            Tunnel::Multiplexed { session, .. } => session.check().await.is_ok(),
+            Tunnel::Subprocess { pid } => is_ssh_process_alive(*pid),
+        }
+    }
+
+    /// Tear the tunnel down: close the ControlMaster connection, or kill the
+    /// subprocess.
+    pub async fn close(self) -> Result<()> {
+        match self {
+            Tunnel::Multiplexed { session, .. } => {
+                session
+                    .close()
+                    .await
+                    .context("Failed to close SSH session")?;
+            }
+            Tunnel::Subprocess { pid } => stop_ssh_tunnel(pid)?,
+        }
+        Ok(())
+    }
+}
+
+/// Confirm a recorded PID is still alive *and* is actually an `ssh` process,
+/// guarding against the OS having reused the PID for something else since we
+/// last saw it.
+pub fn is_ssh_process_alive(pid: u32) -> bool {
+    let mut sys = System::new();
+    sys.refresh_processes();
+    sys.process(Pid::from_u32(pid))
+        .map(|process| process.name().eq_ignore_ascii_case("ssh"))
+        .unwrap_or(false)
+}
+
+/// Directory holding ControlMaster sockets, one per tunnel.
+fn control_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not find home directory")?;
+    let dir = home.join(".region-proxy").join("control");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Start the given SSH forwards, preferring a multiplexed `openssh` session
+/// with a persistent ControlMaster so the connection can be queried for
+/// liveness and cleanly closed later. Falls back to the raw subprocess path
+/// if the control socket can't be established. `forwards` may mix dynamic
+/// SOCKS, local, and remote forwards; UDP specs are relayed over an
+/// underlying TCP forward via `socat`.
+pub async fn start_ssh_tunnel(
+    host: &str,
+    key_path: &Path,
+    forwards: &[ForwardSpec],
+    user: &str,
+) -> Result<Tunnel> {
+    if forwards.is_empty() {
+        bail!("start_ssh_tunnel requires at least one forward");
+    }
+
+    info!(
+        "Starting SSH tunnel to {}@{} with {} forward(s)",
+        user,
+        host,
+        forwards.len()
+    );
+
+    set_key_permissions(key_path)?;
+
+    let mut builder = SessionBuilder::default();
+    builder
+        .known_hosts_check(KnownHosts::Add)
+        .keyfile(key_path)
+        .control_directory(control_dir()?)
+        .server_alive_interval(Duration::from_secs(60));
+
+    match builder.connect_mux(format!("{}@{}", user, host)).await {
+        Ok(session) => {
+            // `openssh` creates the ControlMaster socket at a randomly-named
+            // path inside its own `TempDir` (see `connect_mux`'s internals),
+            // not anything we can compute ourselves, so the real path must
+            // be read back off the session.
+            let control_socket = session.control_socket().to_path_buf();
+            match request_forwards(&session, &control_socket, host, user, forwards).await {
+                Ok(()) => {
+                    info!(
+                        "SSH tunnel established via ControlMaster at {:?}",
+                        control_socket
+                    );
+                    Ok(Tunnel::Multiplexed {
+                        session,
+                        control_socket,
+                    })
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to set up forwards over ControlMaster ({}), falling back to subprocess",
+                        e
+                    );
+                    let _ = session.close().await;
+                    let pid = start_ssh_tunnel_subprocess(host, key_path, forwards, user)?;
+                    Ok(Tunnel::Subprocess { pid })
+                }
+            }
+        }
+        Err(e) => {
+            warn!(
+                "Failed to establish multiplexed SSH session ({}), falling back to subprocess",
+                e
+            );
+            let pid = start_ssh_tunnel_subprocess(host, key_path, forwards, user)?;
+            Ok(Tunnel::Subprocess { pid })
+        }
+    }
+}
+
+/// Request every forward against an already-open ControlMaster socket.
+/// `openssh`'s forwarding API only covers local/remote forwards, not `-D`
+/// dynamic (SOCKS) forwarding, so all forwards are requested the same way
+/// here: directly against the control socket, sharing the one TCP
+/// connection the session established instead of opening a new handshake
+/// per forward.
+async fn request_forwards(
+    session: &openssh::Session,
+    control_socket: &Path,
+    host: &str,
+    user: &str,
+    forwards: &[ForwardSpec],
+) -> Result<()> {
+    for spec in forwards {
+        if spec.is_udp() {
+            setup_udp_relay(session, control_socket, host, user, spec).await?;
+        } else {
+            request_forward(control_socket, host, user, spec)?;
+        }
+    }
+    Ok(())
+}
+
+fn request_forward(control_socket: &Path, host: &str, user: &str, spec: &ForwardSpec) -> Result<()> {
+    let (flag, arg) = spec.ssh_flag()?;
+
+    let status = Command::new("ssh")
+        .arg("-S")
+        .arg(control_socket)
+        .arg("-O")
+        .arg("forward")
+        .arg(flag)
+        .arg(arg)
+        .arg(format!("{}@{}", user, host))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .context("Failed to request forward over control socket")?;
+
+    if !status.success() {
+        bail!("ssh -O forward {} failed", flag);
+    }
+    Ok(())
+}
+
+/// Carry a UDP forward over an underlying TCP forward, bridging each end
+/// with a `socat` relay: `UDP-LISTEN` <-> `TCP` locally, and `TCP-LISTEN`
+/// <-> `UDP` on the remote host (started over the session we already hold).
+async fn setup_udp_relay(
+    session: &openssh::Session,
+    control_socket: &Path,
+    host: &str,
+    user: &str,
+    spec: &ForwardSpec,
+) -> Result<()> {
+    check_socat_available()?;
+
+    let (target_host, target_port) = spec
+        .target
+        .clone()
+        .context("UDP forward needs a target host:port")?;
+
+    match spec.direction {
+        ForwardDirection::LocalToRemote => {
+            // Remote socat bridges a TCP relay port to the real UDP target.
+            session
+                .command("socat")
+                .arg(format!("TCP-LISTEN:{},fork,reuseaddr", spec.bind_port))
+                .arg(format!("UDP:{}:{}", target_host, target_port))
+                .spawn()
+                .await
+                .context("Failed to start remote socat UDP relay")?;
+
+            // Local socat exposes a UDP listener that forwards into the TCP
+            // forward we request next.
+            Command::new("socat")
+                .arg(format!("UDP-LISTEN:{},fork,reuseaddr", spec.bind_port))
+                .arg(format!("TCP:{}:{}", spec.bind_address, spec.bind_port))
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .context("Failed to start local socat UDP relay")?;
+        }
+        ForwardDirection::RemoteToLocal => {
+            // Local socat bridges a TCP relay port to the real UDP target.
+            Command::new("socat")
+                .arg(format!("TCP-LISTEN:{},fork,reuseaddr", spec.bind_port))
+                .arg(format!("UDP:{}:{}", target_host, target_port))
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .context("Failed to start local socat UDP relay")?;
+
+            session
+                .command("socat")
+                .arg(format!("UDP-LISTEN:{},fork,reuseaddr", spec.bind_port))
+                .arg(format!("TCP:{}:{}", spec.bind_address, spec.bind_port))
+                .spawn()
+                .await
+                .context("Failed to start remote socat UDP relay")?;
+        }
+        ForwardDirection::Dynamic => bail!("Dynamic (SOCKS) forwarding doesn't support UDP"),
+    }
+
+    request_forward(control_socket, host, user, &udp_relay_tcp_spec(spec))
+}
+
+/// The TCP forward `request_forward` must make for a UDP spec: it has to
+/// connect to the socat relay `setup_udp_relay` just started above, not to
+/// the real UDP-only target (which isn't reachable over the TCP forward
+/// `ssh` builds). Both relays listen on `spec.bind_port` on loopback, on
+/// whichever side is "the other end" of the SSH forward, so that's what the
+/// TCP spec's target becomes. Split out as a pure function so the fix is
+/// unit-testable without actually spawning `ssh`/`socat`.
+fn udp_relay_tcp_spec(spec: &ForwardSpec) -> ForwardSpec {
+    ForwardSpec {
+        protocol: ForwardProtocol::Tcp,
+        target: Some(("127.0.0.1".to_string(), spec.bind_port)),
+        ..spec.clone()
+    }
+}
+
+fn check_socat_available() -> Result<()> {
+    let found = Command::new("which")
+        .arg("socat")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    if !found {
+        bail!("UDP forwarding requires the `socat` relay helper, which wasn't found on PATH");
+    }
+    Ok(())
+}
+
+fn set_key_permissions(key_path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(key_path)?.permissions();
+        perms.set_mode(0o600);
+        std::fs::set_permissions(key_path, perms)?;
+    }
+    Ok(())
+}
+
+/// Raw `ssh -f -N` subprocess carrying every forward, used when
+/// ControlMaster multiplexing isn't available. UDP specs are rejected here:
+/// without a held-open session there's nowhere to run the remote half of
+/// the `socat` relay, so callers needing UDP must rely on the multiplexed
+/// path.
+fn start_ssh_tunnel_subprocess(
+    host: &str,
+    key_path: &Path,
+    forwards: &[ForwardSpec],
+    user: &str,
+) -> Result<u32> {
+    if forwards.iter().any(ForwardSpec::is_udp) {
+        bail!("UDP forwarding requires the multiplexed ControlMaster session, which is unavailable");
+    }
+
+    let mut cmd = Command::new("ssh");
+    cmd.arg("-f") // Background
+        .arg("-N") // No command
+        .arg("-o")
+        .arg("StrictHostKeyChecking=no")
+        .arg("-o")
+        .arg("UserKnownHostsFile=/dev/null")
+        .arg("-o")
+        .arg("ServerAliveInterval=60")
+        .arg("-o")
+        .arg("ServerAliveCountMax=3")
+        .arg("-i")
+        .arg(key_path);
+
+    for spec in forwards {
+        let (flag, arg) = spec.ssh_flag()?;
+        cmd.arg(flag).arg(arg);
+    }
+
+    let child = cmd
+        .arg(format!("{}@{}", user, host))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start SSH process")?;
+
+    let pid = child.id();
+    info!("SSH tunnel started with PID: {}", pid);
+
+    Ok(pid)
+}
+
+/// Find the SSH process handling the forward on `port`, by scanning process
+/// command lines for a forward argument whose bind port is `port`:
+/// `-D [bind_address:]<port>` or `-L`/`-R [bind_address:]<port>:<host>:<hostport>`.
+/// Dependency-free: no `lsof`/`ps`, so this works the same on Windows and
+/// minimal Linux images.
+pub fn find_ssh_pid(port: u16) -> Result<Option<u32>> {
+    let mut sys = System::new();
+    sys.refresh_processes();
+
+    let needle = port.to_string();
+
+    for (pid, process) in sys.processes() {
+        if !process.name().eq_ignore_ascii_case("ssh") {
+            continue;
+        }
+        let matches = process.cmd().iter().any(|arg| {
+            let fields: Vec<&str> = arg.split(':').collect();
+            let bind_port = match fields.len() {
+                1 | 3 => fields[0],
+                2 | 4 => fields[1],
+                _ => return false,
+            };
+            bind_port == needle
+        });
+        if matches {
+            return Ok(Some(pid.as_u32()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Stop the SSH tunnel by PID
+pub fn stop_ssh_tunnel(pid: u32) -> Result<()> {
+    info!("Stopping SSH tunnel (PID: {})", pid);
+
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        kill(Pid::from_raw(pid as i32), Signal::SIGTERM)
+            .context("Failed to send SIGTERM to SSH process")?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        Command::new("kill")
+            .arg(pid.to_string())
+            .status()
+            .context("Failed to kill SSH process")?;
+    }
+
+    info!("SSH tunnel stopped");
+    Ok(())
+}
+
+/// Stop SSH tunnel by port
+pub fn stop_ssh_tunnel_by_port(port: u16) -> Result<()> {
+    if let Some(pid) = find_ssh_pid(port)? {
+        stop_ssh_tunnel(pid)?;
+    } else {
+        debug!("No SSH process found on port {}", port);
+    }
+    Ok(())
+}
+
+/// Check once whether something is listening on `127.0.0.1:<port>`.
+pub async fn check_port_open(port: u16) -> bool {
+    TcpStream::connect(("127.0.0.1", port)).await.is_ok()
+}
+
+/// Wait for the SSH tunnel to be ready by polling the local forwarded port
+/// with `tokio::net::TcpStream`, backing off exponentially (100ms, capped at
+/// ~2s) over an overall 30s budget. Dependency-free: no `nc`, so this works
+/// the same on Windows and minimal Linux images.
+pub async fn wait_for_tunnel(port: u16) -> Result<()> {
+    info!("Waiting for SSH tunnel to be ready...");
+    wait_for_tcp_port(
+        "127.0.0.1",
+        port,
+        Duration::from_millis(100),
+        Duration::from_secs(2),
+        Duration::from_secs(30),
+    )
+    .await
+    .context("Timeout waiting for SSH tunnel to be ready")?;
+    info!("SSH tunnel is ready");
+    Ok(())
+}
+
+/// Poll `host:port` with `tokio::net::TcpStream`, backing off exponentially
+/// from `initial_delay` up to `max_delay`, over an overall `budget`.
+/// Dependency-free readiness probe: no `nc`, so this works the same on
+/// Windows and minimal Linux images. Exposed so other callers needing a TCP
+/// readiness check (e.g. waiting for SSH on a freshly launched instance) can
+/// reuse the same backoff instead of a fixed sleep.
+pub async fn wait_for_tcp_port(
+    host: &str,
+    port: u16,
+    initial_delay: Duration,
+    max_delay: Duration,
+    budget: Duration,
+) -> Result<()> {
+    let deadline = Instant::now() + budget;
+    let mut delay = initial_delay;
+
+    loop {
+        if TcpStream::connect((host, port)).await.is_ok() {
+            return Ok(());
+        }
+
+        if Instant::now() >= deadline {
+            bail!("Timeout waiting for {}:{} to accept connections", host, port);
+        }
+
+        debug!("{}:{} not ready yet, retrying in {:?}", host, port, delay);
+        sleep(delay).await;
+        delay = (delay * 2).min(max_delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn udp_spec() -> ForwardSpec {
+        ForwardSpec {
+            protocol: ForwardProtocol::Udp,
+            ..ForwardSpec::local_to_remote(5353, "8.8.8.8", 53)
+        }
+    }
+
+    #[test]
+    fn test_udp_relay_tcp_spec_targets_loopback_relay() {
+        let tcp_spec = udp_relay_tcp_spec(&udp_spec());
+        assert_eq!(tcp_spec.protocol, ForwardProtocol::Tcp);
+        assert_eq!(tcp_spec.bind_port, 5353);
+        // Must point at the socat relay on loopback, not the real UDP-only
+        // target: `ssh` can't reach a UDP service over a TCP forward.
+        assert_eq!(tcp_spec.target, Some(("127.0.0.1".to_string(), 5353)));
+    }
+
+    #[test]
+    fn test_subprocess_rejects_udp_forward() {
+        // The bare-subprocess path has no held-open session to run the
+        // remote half of the socat relay on, so a UDP spec must be rejected
+        // here rather than silently produce a broken forward.
+        let result = start_ssh_tunnel_subprocess(
+            "example.com",
+            Path::new("/tmp/region-proxy-test-key"),
+            &[udp_spec()],
+            "ec2-user",
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// One instance within a tracked `FleetState`, enough to reconnect to it or
+/// tear it down in a later invocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetNodeState {
+    pub region: String,
+    pub instance_id: String,
+    pub public_ip: String,
+    pub security_group_id: String,
+    pub key_pair_name: String,
+    pub key_path: PathBuf,
+}
+
+/// A named multi-region fleet launched via `aws::Ec2Fleet`, persisted so
+/// `fleet status`/`fleet destroy` can find it from a separate invocation,
+/// the same way `ProxyState` tracks single-region proxies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetState {
+    pub name: String,
+    pub nodes: Vec<FleetNodeState>,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Manages multiple concurrent `FleetState`s, one file per fleet under
+/// `~/.region-proxy/fleets/`, keyed by fleet name.
+pub struct FleetManager;
+
+impl FleetManager {
+    /// Directory holding one JSON file per active fleet.
+    pub fn fleets_dir() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        let dir = home.join(".region-proxy").join("fleets");
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    fn fleet_path(name: &str) -> Result<PathBuf> {
+        Ok(Self::fleets_dir()?.join(format!("{}.json", name)))
+    }
+
+    /// List all active fleets.
+    pub fn list() -> Result<Vec<FleetState>> {
+        let dir = Self::fleets_dir()?;
+        let mut fleets = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)?;
+            fleets.push(serde_json::from_str(&content)?);
+        }
+        Ok(fleets)
+    }
+
+    /// Get a specific fleet by name.
+    pub fn get(name: &str) -> Result<Option<FleetState>> {
+        let path = Self::fleet_path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Add or overwrite a fleet.
+    pub fn add(state: &FleetState) -> Result<()> {
+        let path = Self::fleet_path(&state.name)?;
+        let content = serde_json::to_string_pretty(state)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Remove a fleet by name.
+    pub fn remove(name: &str) -> Result<()> {
+        let path = Self::fleet_path(name)?;
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
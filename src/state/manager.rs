@@ -0,0 +1,98 @@
+use super::ProxyState;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+/// Manages multiple concurrent `ProxyState` sessions, one file per session
+/// under `~/.region-proxy/sessions/`, keyed by session name. This lets, say,
+/// a Tokyo proxy on 1080 and a Frankfurt proxy on 1081 run side by side
+/// instead of one global proxy.
+pub struct ProxyManager;
+
+impl ProxyManager {
+    /// Directory holding one JSON file per active session.
+    pub fn sessions_dir() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        let dir = home.join(".region-proxy").join("sessions");
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    fn session_path(name: &str) -> Result<PathBuf> {
+        Ok(Self::sessions_dir()?.join(format!("{}.json", name)))
+    }
+
+    /// List all active sessions, migrating the legacy single `state.json`
+    /// into the keyed store first if one is still present.
+    pub fn list() -> Result<Vec<ProxyState>> {
+        Self::migrate_legacy_state()?;
+
+        let dir = Self::sessions_dir()?;
+        let mut sessions = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)?;
+            sessions.push(serde_json::from_str(&content)?);
+        }
+        Ok(sessions)
+    }
+
+    /// Get a specific session by name.
+    pub fn get(name: &str) -> Result<Option<ProxyState>> {
+        Self::migrate_legacy_state()?;
+
+        let path = Self::session_path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Add or overwrite a session.
+    pub fn add(state: &ProxyState) -> Result<()> {
+        let path = Self::session_path(&state.name)?;
+        let content = serde_json::to_string_pretty(state)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Remove a session by name.
+    pub fn remove(name: &str) -> Result<()> {
+        let path = Self::session_path(name)?;
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Number of currently tracked sessions.
+    pub fn running_count() -> Result<usize> {
+        Ok(Self::list()?.len())
+    }
+
+    /// Migrate the legacy single `state.json` into the keyed sessions store,
+    /// so existing users don't lose track of a running tunnel when they
+    /// upgrade. Runs at most once: the legacy file is removed afterwards.
+    /// The legacy file predates named sessions, so it gets a name derived
+    /// from its region and port, same as a fresh session would by default.
+    fn migrate_legacy_state() -> Result<()> {
+        let legacy_path = ProxyState::state_file_path()?;
+        if !legacy_path.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&legacy_path)?;
+        let mut state: ProxyState = serde_json::from_str(&content)?;
+        if state.name.is_empty() {
+            state.name = format!("{}-{}", state.region, state.local_port);
+        }
+        Self::add(&state)?;
+        fs::remove_file(&legacy_path)?;
+        Ok(())
+    }
+}
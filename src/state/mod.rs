@@ -1,11 +1,24 @@
+pub mod fleet;
+pub mod manager;
+
+pub use fleet::{FleetManager, FleetNodeState, FleetState};
+pub use manager::ProxyManager;
+
+use crate::proxy::ForwardSpec;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fs;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProxyState {
+    /// Session name, so multiple proxies can run at once. When the user
+    /// doesn't pass `--name`, defaults to the `--profile` launch profile name
+    /// if one was given, else `<region>-<local_port>`.
+    #[serde(default)]
+    pub name: String,
     pub instance_id: String,
     pub region: String,
     pub public_ip: String,
@@ -14,6 +27,38 @@ pub struct ProxyState {
     pub key_path: PathBuf,
     pub local_port: u16,
     pub ssh_pid: Option<u32>,
+    /// Path to the SSH ControlMaster socket, when the tunnel was set up via
+    /// a multiplexed `openssh` session rather than a bare subprocess.
+    pub control_socket: Option<PathBuf>,
+    /// The forwards currently active over this tunnel, so teardown knows
+    /// what to close.
+    #[serde(default)]
+    pub forwards: Vec<ForwardSpec>,
+    /// Times the tunnel supervisor (`--supervise`) has had to respawn the
+    /// SSH process. Zero when the tunnel wasn't started under supervision.
+    #[serde(default)]
+    pub reconnect_count: u32,
+    /// The supervisor's most recent SSH stderr lines, so `cmd_status` can
+    /// show why the link flapped. Empty when not running under supervision.
+    #[serde(default)]
+    pub recent_log_lines: VecDeque<String>,
+    /// PID of the post-connect `spawn` command from this session's launch
+    /// profile, if any, so `cmd_stop` can tear it down too.
+    #[serde(default)]
+    pub spawn_pid: Option<u32>,
+    /// Allocation id of the Elastic IP associated with this instance, when
+    /// the session was started with `AddressKind::ElasticIp`, so `cmd_stop`
+    /// can release it on teardown instead of leaking it. `None` for every
+    /// other address kind.
+    #[serde(default)]
+    pub elastic_ip_allocation_id: Option<String>,
+    /// PID of the `--supervise` process itself (not the `ssh` child it
+    /// spawns), so `cmd_stop` can signal it to stop reconnecting *before*
+    /// tearing down the EC2 instance, instead of just killing `ssh_pid` out
+    /// from under it and racing the next respawn. `None` when the tunnel
+    /// wasn't started under supervision.
+    #[serde(default)]
+    pub supervisor_pid: Option<u32>,
     pub started_at: DateTime<Utc>,
 }
 
@@ -32,34 +77,35 @@ impl ProxyState {
         Ok(keys_dir)
     }
 
-    pub fn load() -> Result<Option<Self>> {
-        let path = Self::state_file_path()?;
-        if !path.exists() {
-            return Ok(None);
+    /// Check the tunnel's actual health rather than just trusting the state
+    /// file exists: confirm the recorded `ssh_pid` (if any) is still alive
+    /// and really is the ssh process, then confirm the forwarded port is
+    /// still accepting connections.
+    pub async fn health(&self) -> TunnelHealth {
+        if let Some(pid) = self.ssh_pid {
+            if !crate::proxy::tunnel::is_ssh_process_alive(pid) {
+                return TunnelHealth::ProcessDead;
+            }
         }
-        let content = fs::read_to_string(&path)?;
-        let state: Self = serde_json::from_str(&content)?;
-        Ok(Some(state))
-    }
 
-    pub fn save(&self) -> Result<()> {
-        let path = Self::state_file_path()?;
-        let content = serde_json::to_string(self)?;
-        fs::write(&path, content)?;
-        Ok(())
-    }
-
-    pub fn delete() -> Result<()> {
-        let path = Self::state_file_path()?;
-        if path.exists() {
-            fs::remove_file(&path)?;
+        if crate::proxy::tunnel::check_port_open(self.local_port).await {
+            TunnelHealth::Running
+        } else {
+            TunnelHealth::PortClosed
         }
-        Ok(())
     }
+}
 
-    pub fn is_running() -> Result<bool> {
-        Ok(Self::load()?.is_some())
-    }
+/// The observed health of a tunnel, distinguishing a stale state file from a
+/// genuinely live one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunnelHealth {
+    /// The recorded ssh process (if any) is alive and the port is open.
+    Running,
+    /// The ssh process looks alive, but nothing is listening on the port.
+    PortClosed,
+    /// The recorded ssh process is gone (or its PID was reused).
+    ProcessDead,
 }
 
 #[cfg(test)]
@@ -69,6 +115,7 @@ mod tests {
 
     fn create_test_state() -> ProxyState {
         ProxyState {
+            name: "ap-northeast-1-1080".to_string(),
             instance_id: "i-1234567890abcdef0".to_string(),
             region: "ap-northeast-1".to_string(),
             public_ip: "54.150.123.45".to_string(),
@@ -77,6 +124,13 @@ mod tests {
             key_path: PathBuf::from("/tmp/test-key.pem"),
             local_port: 1080,
             ssh_pid: Some(12345),
+            control_socket: None,
+            forwards: vec![ForwardSpec::dynamic(1080)],
+            reconnect_count: 0,
+            recent_log_lines: VecDeque::new(),
+            spawn_pid: None,
+            elastic_ip_allocation_id: None,
+            supervisor_pid: None,
             started_at: Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
         }
     }
@@ -87,6 +141,7 @@ mod tests {
         let json = serde_json::to_string(&state).unwrap();
         let deserialized: ProxyState = serde_json::from_str(&json).unwrap();
 
+        assert_eq!(state.name, deserialized.name);
         assert_eq!(state.instance_id, deserialized.instance_id);
         assert_eq!(state.region, deserialized.region);
         assert_eq!(state.public_ip, deserialized.public_ip);